@@ -0,0 +1,34 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use ilox::scanner::Scanner;
+
+const SNIPPET: &str = r#"// Compute a few fibonacci numbers.
+fun fib(n) {
+    if (n <= 1) return n;
+    return fib(n - 2) + fib(n - 1);
+}
+
+var greeting = "hello, world";
+for (var i = 0; i < 20; i = i + 1) {
+    print fib(i) * 3.14 / 2 >= 10 and !false or nil == 'x';
+}
+"#;
+
+fn generate_source(copies: usize) -> String {
+    SNIPPET.repeat(copies)
+}
+
+fn scan_benchmark(c: &mut Criterion) {
+    let source = generate_source(1_000);
+
+    let mut group = c.benchmark_group("scanner");
+    group.throughput(Throughput::Bytes(source.len() as u64));
+    group.bench_function("scan_tokens", |b| {
+        b.iter(|| Scanner::new(black_box(source.clone())).scan_tokens())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, scan_benchmark);
+criterion_main!(benches);
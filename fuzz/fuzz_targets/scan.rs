@@ -0,0 +1,18 @@
+//! Feeds arbitrary input to the scanner. Malformed source must come back as
+//! `ScannerResult::Error`; any panic is a bug.
+//!
+//! Run from the repository root with a nightly toolchain:
+//!
+//! ```sh
+//! cargo install cargo-fuzz
+//! cargo +nightly fuzz run scan
+//! ```
+#![no_main]
+
+use ilox::scanner::Scanner;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let source = String::from_utf8_lossy(data).into_owned();
+    Scanner::new(source).scan_tokens();
+});
@@ -0,0 +1,71 @@
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    interpreter::{Interpreter, RuntimeError},
+    value::Value,
+};
+
+pub trait Callable: fmt::Debug + fmt::Display {
+    fn arity(&self) -> usize;
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: &[Value],
+    ) -> Result<Value, RuntimeError>;
+}
+
+type NativeFn = fn(&mut Interpreter, &[Value]) -> Result<Value, RuntimeError>;
+
+#[derive(Debug)]
+pub struct NativeFunction {
+    name: &'static str,
+    arity: usize,
+    function: NativeFn,
+}
+
+impl NativeFunction {
+    pub fn new(name: &'static str, arity: usize, function: NativeFn) -> Self {
+        Self {
+            name,
+            arity,
+            function,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl fmt::Display for NativeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<native fn {}>", self.name)
+    }
+}
+
+impl Callable for NativeFunction {
+    fn arity(&self) -> usize {
+        self.arity
+    }
+
+    fn call(
+        &self,
+        interpreter: &mut Interpreter,
+        arguments: &[Value],
+    ) -> Result<Value, RuntimeError> {
+        (self.function)(interpreter, arguments)
+    }
+}
+
+pub fn clock() -> NativeFunction {
+    NativeFunction::new("clock", 0, |_, _| {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs_f64())
+            .unwrap_or(0.0);
+        Ok(Value::Number(seconds))
+    })
+}
@@ -0,0 +1,110 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{interpreter::RuntimeError, scanner::Token, value::Value};
+
+#[derive(Debug, Default)]
+pub struct Environment {
+    values: HashMap<String, Value>,
+    enclosing: Option<Rc<RefCell<Environment>>>,
+}
+
+impl Environment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_enclosing(enclosing: Rc<RefCell<Environment>>) -> Self {
+        Self {
+            values: HashMap::new(),
+            enclosing: Some(enclosing),
+        }
+    }
+
+    pub fn define(&mut self, name: &str, value: Value) {
+        self.values.insert(name.to_string(), value);
+    }
+
+    pub fn get(&self, name: &Token) -> Result<Value, RuntimeError> {
+        if let Some(value) = self.values.get(&name.to_string()) {
+            return Ok(value.clone());
+        }
+
+        match &self.enclosing {
+            Some(enclosing) => enclosing.borrow().get(name),
+            None => Err(undefined(name)),
+        }
+    }
+
+    pub fn assign(&mut self, name: &Token, value: Value) -> Result<(), RuntimeError> {
+        if let Some(slot) = self.values.get_mut(&name.to_string()) {
+            *slot = value;
+            return Ok(());
+        }
+
+        match &self.enclosing {
+            Some(enclosing) => enclosing.borrow_mut().assign(name, value),
+            None => Err(undefined(name)),
+        }
+    }
+}
+
+fn undefined(name: &Token) -> RuntimeError {
+    RuntimeError::new(name.clone(), format!("undefined variable '{}'", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scanner::TokenType;
+
+    use super::*;
+
+    fn identifier(name: &str) -> Token {
+        Token::new(TokenType::Identifier, name.to_string(), 1, None)
+    }
+
+    #[test]
+    fn test_define_and_get() {
+        let mut env = Environment::new();
+        env.define("a", Value::Number(1.0));
+        assert_eq!(env.get(&identifier("a")).unwrap(), Value::Number(1.0));
+
+        env.define("a", Value::Bool(true));
+        assert_eq!(env.get(&identifier("a")).unwrap(), Value::Bool(true));
+    }
+
+    #[test]
+    fn test_get_undefined() {
+        let env = Environment::new();
+        let error = env.get(&identifier("missing")).unwrap_err();
+        assert_eq!(error.message, "undefined variable 'missing'");
+    }
+
+    #[test]
+    fn test_assign() {
+        let mut env = Environment::new();
+        let error = env.assign(&identifier("a"), Value::Nil).unwrap_err();
+        assert_eq!(error.message, "undefined variable 'a'");
+
+        env.define("a", Value::Nil);
+        env.assign(&identifier("a"), Value::Number(2.0)).unwrap();
+        assert_eq!(env.get(&identifier("a")).unwrap(), Value::Number(2.0));
+    }
+
+    #[test]
+    fn test_enclosing_lookup_and_assign() {
+        let outer = Rc::new(RefCell::new(Environment::new()));
+        outer.borrow_mut().define("a", Value::Number(1.0));
+
+        let mut inner = Environment::with_enclosing(Rc::clone(&outer));
+        assert_eq!(inner.get(&identifier("a")).unwrap(), Value::Number(1.0));
+
+        inner.assign(&identifier("a"), Value::Number(2.0)).unwrap();
+        assert_eq!(
+            outer.borrow().get(&identifier("a")).unwrap(),
+            Value::Number(2.0)
+        );
+
+        inner.define("b", Value::Nil);
+        assert!(outer.borrow().get(&identifier("b")).is_err());
+    }
+}
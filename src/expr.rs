@@ -1,6 +1,7 @@
-use std::any::Any;
-
-use crate::scanner::{LiteralValue, Token};
+use crate::{
+    scanner::{LiteralValue, Token, TokenType},
+    stmt::{Stmt, Visitable as _},
+};
 
 pub trait ExprVisitor {
     type Output;
@@ -9,6 +10,19 @@ pub trait ExprVisitor {
     fn visit_grouping(&mut self, expr: &Grouping) -> Self::Output;
     fn visit_literal(&mut self, expr: &Literal) -> Self::Output;
     fn visit_unary(&mut self, expr: &Unary) -> Self::Output;
+    fn visit_variable(&mut self, expr: &Variable) -> Self::Output;
+    fn visit_assign(&mut self, expr: &Assign) -> Self::Output;
+    fn visit_logical(&mut self, expr: &Logical) -> Self::Output;
+    fn visit_conditional(&mut self, expr: &Conditional) -> Self::Output;
+    fn visit_call(&mut self, expr: &Call) -> Self::Output;
+    fn visit_get(&mut self, expr: &Get) -> Self::Output;
+    fn visit_set(&mut self, expr: &Set) -> Self::Output;
+    fn visit_this(&mut self, expr: &This) -> Self::Output;
+    fn visit_super(&mut self, expr: &Super) -> Self::Output;
+    fn visit_function(&mut self, expr: &Function) -> Self::Output;
+    fn visit_array(&mut self, expr: &Array) -> Self::Output;
+    fn visit_index(&mut self, expr: &Index) -> Self::Output;
+    fn visit_map(&mut self, expr: &Map) -> Self::Output;
 }
 
 pub struct AstPrinter;
@@ -42,17 +56,522 @@ impl ExprVisitor for AstPrinter {
     fn visit_unary(&mut self, expr: &Unary) -> Self::Output {
         format!("({} {})", expr.op, expr.right.accept(self))
     }
+
+    fn visit_variable(&mut self, expr: &Variable) -> Self::Output {
+        format!("{}", expr.name)
+    }
+
+    fn visit_assign(&mut self, expr: &Assign) -> Self::Output {
+        format!("(= {} {})", expr.name, expr.value.accept(self))
+    }
+
+    fn visit_logical(&mut self, expr: &Logical) -> Self::Output {
+        format!(
+            "({} {} {})",
+            expr.op,
+            expr.left.accept(self),
+            expr.right.accept(self)
+        )
+    }
+
+    fn visit_conditional(&mut self, expr: &Conditional) -> Self::Output {
+        format!(
+            "(?: {} {} {})",
+            expr.condition.accept(self),
+            expr.then_branch.accept(self),
+            expr.else_branch.accept(self)
+        )
+    }
+
+    fn visit_call(&mut self, expr: &Call) -> Self::Output {
+        let mut parts = vec!["call".to_string(), expr.callee.accept(self)];
+        parts.extend(expr.args.iter().map(|arg| arg.accept(self)));
+        format!("({})", parts.join(" "))
+    }
+
+    fn visit_get(&mut self, expr: &Get) -> Self::Output {
+        format!("(. {} {})", expr.object.accept(self), expr.name)
+    }
+
+    fn visit_set(&mut self, expr: &Set) -> Self::Output {
+        format!(
+            "(.= {} {} {})",
+            expr.object.accept(self),
+            expr.name,
+            expr.value.accept(self)
+        )
+    }
+
+    fn visit_this(&mut self, expr: &This) -> Self::Output {
+        format!("{}", expr.keyword)
+    }
+
+    fn visit_super(&mut self, expr: &Super) -> Self::Output {
+        format!("({} {})", expr.keyword, expr.method)
+    }
+
+    fn visit_function(&mut self, expr: &Function) -> Self::Output {
+        let params: Vec<String> = expr.params.iter().map(|p| p.to_string()).collect();
+        format!("(fun ({}))", params.join(" "))
+    }
+
+    fn visit_array(&mut self, expr: &Array) -> Self::Output {
+        let elements: Vec<String> = expr.elements.iter().map(|e| e.accept(self)).collect();
+        format!("[{}]", elements.join(", "))
+    }
+
+    fn visit_index(&mut self, expr: &Index) -> Self::Output {
+        format!(
+            "(index {} {})",
+            expr.object.accept(self),
+            expr.index.accept(self)
+        )
+    }
+
+    fn visit_map(&mut self, expr: &Map) -> Self::Output {
+        let entries: Vec<String> = expr
+            .entries
+            .iter()
+            .map(|(key, value)| format!("{}: {}", key.accept(self), value.accept(self)))
+            .collect();
+        format!("{{{}}}", entries.join(", "))
+    }
+}
+
+pub struct RpnPrinter;
+
+impl RpnPrinter {
+    pub fn print(expr: &Expression) -> String {
+        expr.accept(&mut RpnPrinter)
+    }
+
+    fn postfix(&mut self, operands: &[&Expression], op: String) -> String {
+        let mut parts: Vec<String> = operands.iter().map(|e| e.accept(self)).collect();
+        parts.push(op);
+        parts.join(" ")
+    }
+}
+
+// Operators that take a variable number of operands carry their count, e.g. `f 1 2 call/2`.
+impl ExprVisitor for RpnPrinter {
+    type Output = String;
+
+    fn visit_binary(&mut self, expr: &Binary) -> Self::Output {
+        self.postfix(&[&expr.left, &expr.right], expr.op.to_string())
+    }
+
+    fn visit_grouping(&mut self, expr: &Grouping) -> Self::Output {
+        expr.expr.accept(self)
+    }
+
+    fn visit_literal(&mut self, expr: &Literal) -> Self::Output {
+        format!("{}", expr.value)
+    }
+
+    fn visit_unary(&mut self, expr: &Unary) -> Self::Output {
+        // `~` keeps negation distinct from binary subtraction.
+        let op = match expr.op.token_type {
+            TokenType::Minus => "~".to_string(),
+            _ => expr.op.to_string(),
+        };
+        self.postfix(&[&expr.right], op)
+    }
+
+    fn visit_variable(&mut self, expr: &Variable) -> Self::Output {
+        format!("{}", expr.name)
+    }
+
+    fn visit_assign(&mut self, expr: &Assign) -> Self::Output {
+        format!("{} {} =", expr.value.accept(self), expr.name)
+    }
+
+    fn visit_logical(&mut self, expr: &Logical) -> Self::Output {
+        self.postfix(&[&expr.left, &expr.right], expr.op.to_string())
+    }
+
+    fn visit_conditional(&mut self, expr: &Conditional) -> Self::Output {
+        let operands = [&*expr.condition, &*expr.then_branch, &*expr.else_branch];
+        self.postfix(&operands, "?:".to_string())
+    }
+
+    fn visit_call(&mut self, expr: &Call) -> Self::Output {
+        let mut operands = vec![&*expr.callee];
+        operands.extend(expr.args.iter());
+        self.postfix(&operands, format!("call/{}", expr.args.len()))
+    }
+
+    fn visit_get(&mut self, expr: &Get) -> Self::Output {
+        format!("{} {} .", expr.object.accept(self), expr.name)
+    }
+
+    fn visit_set(&mut self, expr: &Set) -> Self::Output {
+        format!(
+            "{} {} {} .=",
+            expr.object.accept(self),
+            expr.value.accept(self),
+            expr.name
+        )
+    }
+
+    fn visit_this(&mut self, expr: &This) -> Self::Output {
+        format!("{}", expr.keyword)
+    }
+
+    fn visit_super(&mut self, expr: &Super) -> Self::Output {
+        format!("{} {}", expr.method, expr.keyword)
+    }
+
+    fn visit_function(&mut self, expr: &Function) -> Self::Output {
+        let mut parts: Vec<String> = expr.params.iter().map(|p| p.to_string()).collect();
+        parts.push(format!("fun/{}", expr.params.len()));
+        parts.join(" ")
+    }
+
+    fn visit_array(&mut self, expr: &Array) -> Self::Output {
+        let operands: Vec<&Expression> = expr.elements.iter().collect();
+        self.postfix(&operands, format!("array/{}", expr.elements.len()))
+    }
+
+    fn visit_index(&mut self, expr: &Index) -> Self::Output {
+        self.postfix(&[&expr.object, &expr.index], "index".to_string())
+    }
+
+    fn visit_map(&mut self, expr: &Map) -> Self::Output {
+        let operands: Vec<&Expression> = expr
+            .entries
+            .iter()
+            .flat_map(|(key, value)| [key, value])
+            .collect();
+        self.postfix(&operands, format!("map/{}", expr.entries.len()))
+    }
+}
+
+pub struct JsonPrinter;
+
+impl JsonPrinter {
+    pub fn print(expr: &Expression) -> String {
+        expr.accept(&mut JsonPrinter)
+    }
+
+    pub(crate) fn node(node_type: &str, fields: Vec<(&str, String)>) -> String {
+        let mut members = vec![format!("\"type\":{}", JsonPrinter::string(node_type))];
+        members.extend(
+            fields
+                .into_iter()
+                .map(|(key, value)| format!("{}:{}", JsonPrinter::string(key), value)),
+        );
+        format!("{{{}}}", members.join(","))
+    }
+
+    pub(crate) fn list(items: Vec<String>) -> String {
+        format!("[{}]", items.join(","))
+    }
+
+    pub(crate) fn string(value: &str) -> String {
+        let mut out = String::from("\"");
+        for c in value.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+        out
+    }
+
+    pub(crate) fn token(token: &Token) -> String {
+        JsonPrinter::string(&token.to_string())
+    }
+
+    fn expressions(&mut self, exprs: &[Expression]) -> String {
+        JsonPrinter::list(exprs.iter().map(|e| e.accept(self)).collect())
+    }
+}
+
+impl ExprVisitor for JsonPrinter {
+    type Output = String;
+
+    fn visit_binary(&mut self, expr: &Binary) -> Self::Output {
+        let fields = vec![
+            ("operator", JsonPrinter::token(&expr.op)),
+            ("left", expr.left.accept(self)),
+            ("right", expr.right.accept(self)),
+        ];
+        JsonPrinter::node("Binary", fields)
+    }
+
+    fn visit_grouping(&mut self, expr: &Grouping) -> Self::Output {
+        JsonPrinter::node("Grouping", vec![("expression", expr.expr.accept(self))])
+    }
+
+    fn visit_literal(&mut self, expr: &Literal) -> Self::Output {
+        let value = match &expr.value {
+            // JSON has no representation for infinities or NaN.
+            LiteralValue::Number(n) if !n.is_finite() => "null".to_string(),
+            LiteralValue::Number(n) => n.to_string(),
+            LiteralValue::Str(s) => JsonPrinter::string(s),
+            LiteralValue::Char(c) => JsonPrinter::string(&c.to_string()),
+            LiteralValue::Bool(b) => b.to_string(),
+            LiteralValue::Nil => "null".to_string(),
+        };
+        JsonPrinter::node("Literal", vec![("value", value)])
+    }
+
+    fn visit_unary(&mut self, expr: &Unary) -> Self::Output {
+        let fields = vec![
+            ("operator", JsonPrinter::token(&expr.op)),
+            ("right", expr.right.accept(self)),
+        ];
+        JsonPrinter::node("Unary", fields)
+    }
+
+    fn visit_variable(&mut self, expr: &Variable) -> Self::Output {
+        JsonPrinter::node("Variable", vec![("name", JsonPrinter::token(&expr.name))])
+    }
+
+    fn visit_assign(&mut self, expr: &Assign) -> Self::Output {
+        let fields = vec![
+            ("name", JsonPrinter::token(&expr.name)),
+            ("value", expr.value.accept(self)),
+        ];
+        JsonPrinter::node("Assign", fields)
+    }
+
+    fn visit_logical(&mut self, expr: &Logical) -> Self::Output {
+        let fields = vec![
+            ("operator", JsonPrinter::token(&expr.op)),
+            ("left", expr.left.accept(self)),
+            ("right", expr.right.accept(self)),
+        ];
+        JsonPrinter::node("Logical", fields)
+    }
+
+    fn visit_conditional(&mut self, expr: &Conditional) -> Self::Output {
+        let fields = vec![
+            ("condition", expr.condition.accept(self)),
+            ("then", expr.then_branch.accept(self)),
+            ("else", expr.else_branch.accept(self)),
+        ];
+        JsonPrinter::node("Conditional", fields)
+    }
+
+    fn visit_call(&mut self, expr: &Call) -> Self::Output {
+        let fields = vec![
+            ("callee", expr.callee.accept(self)),
+            ("arguments", self.expressions(&expr.args)),
+        ];
+        JsonPrinter::node("Call", fields)
+    }
+
+    fn visit_get(&mut self, expr: &Get) -> Self::Output {
+        let fields = vec![
+            ("object", expr.object.accept(self)),
+            ("name", JsonPrinter::token(&expr.name)),
+        ];
+        JsonPrinter::node("Get", fields)
+    }
+
+    fn visit_set(&mut self, expr: &Set) -> Self::Output {
+        let fields = vec![
+            ("object", expr.object.accept(self)),
+            ("name", JsonPrinter::token(&expr.name)),
+            ("value", expr.value.accept(self)),
+        ];
+        JsonPrinter::node("Set", fields)
+    }
+
+    fn visit_this(&mut self, _expr: &This) -> Self::Output {
+        JsonPrinter::node("This", Vec::new())
+    }
+
+    fn visit_super(&mut self, expr: &Super) -> Self::Output {
+        JsonPrinter::node("Super", vec![("method", JsonPrinter::token(&expr.method))])
+    }
+
+    fn visit_function(&mut self, expr: &Function) -> Self::Output {
+        let params = expr.params.iter().map(JsonPrinter::token).collect();
+        let body = expr.body.iter().map(|stmt| stmt.accept(self)).collect();
+        let fields = vec![
+            ("params", JsonPrinter::list(params)),
+            ("body", JsonPrinter::list(body)),
+        ];
+        JsonPrinter::node("Function", fields)
+    }
+
+    fn visit_array(&mut self, expr: &Array) -> Self::Output {
+        JsonPrinter::node(
+            "Array",
+            vec![("elements", self.expressions(&expr.elements))],
+        )
+    }
+
+    fn visit_index(&mut self, expr: &Index) -> Self::Output {
+        let fields = vec![
+            ("object", expr.object.accept(self)),
+            ("index", expr.index.accept(self)),
+        ];
+        JsonPrinter::node("Index", fields)
+    }
+
+    fn visit_map(&mut self, expr: &Map) -> Self::Output {
+        let entries = expr
+            .entries
+            .iter()
+            .map(|(key, value)| {
+                let key = key.accept(self);
+                let value = value.accept(self);
+                format!("{{\"key\":{},\"value\":{}}}", key, value)
+            })
+            .collect();
+        JsonPrinter::node("Map", vec![("entries", JsonPrinter::list(entries))])
+    }
+}
+
+#[derive(Default)]
+pub struct DotPrinter {
+    nodes: Vec<String>,
+    edges: Vec<String>,
+}
+
+impl DotPrinter {
+    pub fn print(expr: &Expression) -> String {
+        let mut printer = DotPrinter::default();
+        expr.accept(&mut printer);
+
+        let mut out = String::from("digraph AST {\n");
+        for line in printer.nodes.iter().chain(printer.edges.iter()) {
+            out.push_str("    ");
+            out.push_str(line);
+            out.push('\n');
+        }
+        out.push('}');
+        out
+    }
+
+    // Numbers the parent before its children so ids read top-down.
+    fn tree(&mut self, label: String, children: &[&Expression]) -> usize {
+        let id = self.nodes.len();
+        let label = label.replace('\\', "\\\\").replace('"', "\\\"");
+        self.nodes.push(format!("n{} [label=\"{}\"];", id, label));
+
+        for child in children {
+            let child_id = child.accept(self);
+            self.edges.push(format!("n{} -> n{};", id, child_id));
+        }
+        id
+    }
+}
+
+impl ExprVisitor for DotPrinter {
+    type Output = usize;
+
+    fn visit_binary(&mut self, expr: &Binary) -> Self::Output {
+        self.tree(expr.op.to_string(), &[&expr.left, &expr.right])
+    }
+
+    fn visit_grouping(&mut self, expr: &Grouping) -> Self::Output {
+        self.tree("group".to_string(), &[&expr.expr])
+    }
+
+    fn visit_literal(&mut self, expr: &Literal) -> Self::Output {
+        self.tree(expr.value.to_string(), &[])
+    }
+
+    fn visit_unary(&mut self, expr: &Unary) -> Self::Output {
+        self.tree(expr.op.to_string(), &[&expr.right])
+    }
+
+    fn visit_variable(&mut self, expr: &Variable) -> Self::Output {
+        self.tree(expr.name.to_string(), &[])
+    }
+
+    fn visit_assign(&mut self, expr: &Assign) -> Self::Output {
+        self.tree(format!("= {}", expr.name), &[&expr.value])
+    }
+
+    fn visit_logical(&mut self, expr: &Logical) -> Self::Output {
+        self.tree(expr.op.to_string(), &[&expr.left, &expr.right])
+    }
+
+    fn visit_conditional(&mut self, expr: &Conditional) -> Self::Output {
+        let children = [&*expr.condition, &*expr.then_branch, &*expr.else_branch];
+        self.tree("?:".to_string(), &children)
+    }
+
+    fn visit_call(&mut self, expr: &Call) -> Self::Output {
+        let mut children = vec![&*expr.callee];
+        children.extend(expr.args.iter());
+        self.tree("call".to_string(), &children)
+    }
+
+    fn visit_get(&mut self, expr: &Get) -> Self::Output {
+        self.tree(format!(". {}", expr.name), &[&expr.object])
+    }
+
+    fn visit_set(&mut self, expr: &Set) -> Self::Output {
+        self.tree(format!(".= {}", expr.name), &[&expr.object, &expr.value])
+    }
+
+    fn visit_this(&mut self, expr: &This) -> Self::Output {
+        self.tree(expr.keyword.to_string(), &[])
+    }
+
+    fn visit_super(&mut self, expr: &Super) -> Self::Output {
+        self.tree(format!("{}.{}", expr.keyword, expr.method), &[])
+    }
+
+    fn visit_function(&mut self, expr: &Function) -> Self::Output {
+        let params: Vec<String> = expr.params.iter().map(|p| p.to_string()).collect();
+        self.tree(format!("fun ({})", params.join(" ")), &[])
+    }
+
+    fn visit_array(&mut self, expr: &Array) -> Self::Output {
+        let children: Vec<&Expression> = expr.elements.iter().collect();
+        self.tree("[]".to_string(), &children)
+    }
+
+    fn visit_index(&mut self, expr: &Index) -> Self::Output {
+        self.tree("index".to_string(), &[&expr.object, &expr.index])
+    }
+
+    fn visit_map(&mut self, expr: &Map) -> Self::Output {
+        let children: Vec<&Expression> = expr
+            .entries
+            .iter()
+            .flat_map(|(key, value)| [key, value])
+            .collect();
+        self.tree("{}".to_string(), &children)
+    }
 }
 
-trait Visitable {
+pub trait Visitable {
     fn accept<V: ExprVisitor>(&self, visitor: &mut V) -> V::Output;
 }
 
+#[derive(Debug, Clone)]
 pub enum Expression {
     Binary(Binary),
     Grouping(Grouping),
     Literal(Literal),
     Unary(Unary),
+    Variable(Variable),
+    Assign(Assign),
+    Logical(Logical),
+    Conditional(Conditional),
+    Call(Call),
+    Get(Get),
+    Set(Set),
+    This(This),
+    Super(Super),
+    Function(Function),
+    Array(Array),
+    Index(Index),
+    Map(Map),
 }
 
 impl Visitable for Expression {
@@ -62,14 +581,52 @@ impl Visitable for Expression {
             Expression::Grouping(expr) => visitor.visit_grouping(expr),
             Expression::Literal(expr) => visitor.visit_literal(expr),
             Expression::Unary(expr) => visitor.visit_unary(expr),
+            Expression::Variable(expr) => visitor.visit_variable(expr),
+            Expression::Assign(expr) => visitor.visit_assign(expr),
+            Expression::Logical(expr) => visitor.visit_logical(expr),
+            Expression::Conditional(expr) => visitor.visit_conditional(expr),
+            Expression::Call(expr) => visitor.visit_call(expr),
+            Expression::Get(expr) => visitor.visit_get(expr),
+            Expression::Set(expr) => visitor.visit_set(expr),
+            Expression::This(expr) => visitor.visit_this(expr),
+            Expression::Super(expr) => visitor.visit_super(expr),
+            Expression::Function(expr) => visitor.visit_function(expr),
+            Expression::Array(expr) => visitor.visit_array(expr),
+            Expression::Index(expr) => visitor.visit_index(expr),
+            Expression::Map(expr) => visitor.visit_map(expr),
         }
     }
 }
 
+impl Expression {
+    pub fn line(&self) -> usize {
+        match self {
+            Expression::Binary(expr) => expr.op.line(),
+            Expression::Grouping(expr) => expr.line,
+            Expression::Literal(expr) => expr.line,
+            Expression::Unary(expr) => expr.op.line(),
+            Expression::Variable(expr) => expr.name.line(),
+            Expression::Assign(expr) => expr.name.line(),
+            Expression::Logical(expr) => expr.op.line(),
+            Expression::Conditional(expr) => expr.condition.line(),
+            Expression::Call(expr) => expr.paren.line(),
+            Expression::Get(expr) => expr.name.line(),
+            Expression::Set(expr) => expr.name.line(),
+            Expression::This(expr) => expr.keyword.line(),
+            Expression::Super(expr) => expr.keyword.line(),
+            Expression::Function(expr) => expr.line,
+            Expression::Array(expr) => expr.line,
+            Expression::Index(expr) => expr.bracket.line(),
+            Expression::Map(expr) => expr.line,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Binary {
-    left: Box<Expression>,
-    op: Token,
-    right: Box<Expression>,
+    pub left: Box<Expression>,
+    pub op: Token,
+    pub right: Box<Expression>,
 }
 
 impl Binary {
@@ -78,23 +635,34 @@ impl Binary {
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct Grouping {
-    expr: Box<Expression>,
+    pub expr: Box<Expression>,
+    pub line: usize,
+}
+
+impl Grouping {
+    pub fn new(expr: Box<Expression>, line: usize) -> Self {
+        Self { expr, line }
+    }
 }
 
+#[derive(Debug, Clone)]
 pub struct Literal {
-    value: LiteralValue,
+    pub value: LiteralValue,
+    pub line: usize,
 }
 
 impl Literal {
-    pub fn new(value: LiteralValue) -> Self {
-        Self { value }
+    pub fn new(value: LiteralValue, line: usize) -> Self {
+        Self { value, line }
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct Unary {
-    op: Token,
-    right: Box<Expression>,
+    pub op: Token,
+    pub right: Box<Expression>,
 }
 
 impl Unary {
@@ -103,12 +671,214 @@ impl Unary {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct Variable {
+    pub name: Token,
+}
+
+impl Variable {
+    pub fn new(name: Token) -> Self {
+        Self { name }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Assign {
+    pub name: Token,
+    pub value: Box<Expression>,
+}
+
+impl Assign {
+    pub fn new(name: Token, value: Box<Expression>) -> Self {
+        Self { name, value }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Logical {
+    pub left: Box<Expression>,
+    pub op: Token,
+    pub right: Box<Expression>,
+}
+
+impl Logical {
+    pub fn new(left: Box<Expression>, op: Token, right: Box<Expression>) -> Self {
+        Self { left, op, right }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Conditional {
+    pub condition: Box<Expression>,
+    pub then_branch: Box<Expression>,
+    pub else_branch: Box<Expression>,
+}
+
+impl Conditional {
+    pub fn new(
+        condition: Box<Expression>,
+        then_branch: Box<Expression>,
+        else_branch: Box<Expression>,
+    ) -> Self {
+        Self {
+            condition,
+            then_branch,
+            else_branch,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Call {
+    pub callee: Box<Expression>,
+    pub paren: Token,
+    pub args: Vec<Expression>,
+}
+
+impl Call {
+    pub fn new(callee: Box<Expression>, paren: Token, args: Vec<Expression>) -> Self {
+        Self {
+            callee,
+            paren,
+            args,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Get {
+    pub object: Box<Expression>,
+    pub name: Token,
+}
+
+impl Get {
+    pub fn new(object: Box<Expression>, name: Token) -> Self {
+        Self { object, name }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Set {
+    pub object: Box<Expression>,
+    pub name: Token,
+    pub value: Box<Expression>,
+}
+
+impl Set {
+    pub fn new(object: Box<Expression>, name: Token, value: Box<Expression>) -> Self {
+        Self {
+            object,
+            name,
+            value,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct This {
+    pub keyword: Token,
+}
+
+impl This {
+    pub fn new(keyword: Token) -> Self {
+        Self { keyword }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Super {
+    pub keyword: Token,
+    pub method: Token,
+}
+
+impl Super {
+    pub fn new(keyword: Token, method: Token) -> Self {
+        Self { keyword, method }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub params: Vec<Token>,
+    pub body: Vec<Stmt>,
+    pub line: usize,
+}
+
+impl Function {
+    pub fn new(params: Vec<Token>, body: Vec<Stmt>, line: usize) -> Self {
+        Self { params, body, line }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Array {
+    pub elements: Vec<Expression>,
+    pub line: usize,
+}
+
+impl Array {
+    pub fn new(elements: Vec<Expression>, line: usize) -> Self {
+        Self { elements, line }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Index {
+    pub object: Box<Expression>,
+    pub index: Box<Expression>,
+    pub bracket: Token,
+}
+
+impl Index {
+    pub fn new(object: Box<Expression>, index: Box<Expression>, bracket: Token) -> Self {
+        Self {
+            object,
+            index,
+            bracket,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Map {
+    pub entries: Vec<(Expression, Expression)>,
+    pub line: usize,
+}
+
+impl Map {
+    pub fn new(entries: Vec<(Expression, Expression)>, line: usize) -> Self {
+        Self { entries, line }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::scanner::TokenType;
+    use crate::{
+        parser,
+        scanner::{Scanner, TokenType},
+    };
 
     use super::*;
 
+    fn token(token_type: TokenType, lexeme: &str) -> Token {
+        Token::new(token_type, lexeme.to_string(), 1, None)
+    }
+
+    fn identifier(name: &str) -> Token {
+        token(TokenType::Identifier, name)
+    }
+
+    fn number(n: f64) -> Box<Expression> {
+        Box::new(Expression::Literal(Literal::new(
+            LiteralValue::Number(n),
+            1,
+        )))
+    }
+
+    fn variable(name: &str) -> Box<Expression> {
+        Box::new(Expression::Variable(Variable::new(identifier(name))))
+    }
+
     #[test]
     fn test_printer() {
         let expr = Expression::Binary(Binary {
@@ -116,17 +886,245 @@ mod tests {
                 op: Token::new(TokenType::Minus, String::from("-"), 0, None),
                 right: Box::new(Expression::Literal(Literal {
                     value: LiteralValue::Number(123.0),
+                    line: 0,
                 })),
             })),
             op: Token::new(TokenType::Star, String::from("*"), 0, None),
             right: Box::new(Expression::Grouping(Grouping {
                 expr: Box::new(Expression::Literal(Literal {
                     value: LiteralValue::Number(45.67), // Changed from Int(2) to Number(45.67)
+                    line: 0,
                 })),
+                line: 0,
             })),
         });
 
         // The expected output should match what the Java version would produce
         assert_eq!(AstPrinter::print(&expr), "(* (- 123) (group 45.67))");
     }
+
+    #[test]
+    fn test_print_variable() {
+        let expr = Expression::Variable(Variable::new(identifier("foo")));
+        assert_eq!(AstPrinter::print(&expr), "foo");
+    }
+
+    #[test]
+    fn test_print_assign() {
+        let sum = Expression::Binary(Binary::new(
+            number(1.0),
+            token(TokenType::Plus, "+"),
+            number(2.0),
+        ));
+        let expr = Expression::Assign(Assign::new(identifier("x"), Box::new(sum)));
+        assert_eq!(AstPrinter::print(&expr), "(= x (+ 1 2))");
+    }
+
+    #[test]
+    fn test_print_logical() {
+        let expr = Expression::Logical(Logical::new(
+            variable("a"),
+            token(TokenType::And, "and"),
+            variable("b"),
+        ));
+        assert_eq!(AstPrinter::print(&expr), "(and a b)");
+    }
+
+    #[test]
+    fn test_print_call() {
+        let expr = Expression::Call(Call::new(
+            variable("f"),
+            token(TokenType::RightParen, ")"),
+            vec![*number(1.0), *number(2.0)],
+        ));
+        assert_eq!(AstPrinter::print(&expr), "(call f 1 2)");
+    }
+
+    #[test]
+    fn test_print_get_and_set() {
+        let get = Expression::Get(Get::new(variable("a"), identifier("b")));
+        assert_eq!(AstPrinter::print(&get), "(. a b)");
+
+        let set = Expression::Set(Set::new(variable("a"), identifier("b"), number(3.0)));
+        assert_eq!(AstPrinter::print(&set), "(.= a b 3)");
+    }
+
+    #[test]
+    fn test_print_this_and_super() {
+        let this = Expression::This(This::new(token(TokenType::This, "this")));
+        assert_eq!(AstPrinter::print(&this), "this");
+
+        let sup = Expression::Super(Super::new(
+            token(TokenType::Super, "super"),
+            identifier("init"),
+        ));
+        assert_eq!(AstPrinter::print(&sup), "(super init)");
+    }
+
+    #[test]
+    fn test_print_conditional() {
+        let expr = Expression::Conditional(Conditional::new(
+            variable("a"),
+            variable("b"),
+            variable("c"),
+        ));
+        assert_eq!(AstPrinter::print(&expr), "(?: a b c)");
+    }
+
+    #[test]
+    fn test_clone_prints_identically() {
+        let expr = Expression::Binary(Binary::new(
+            Box::new(Expression::Grouping(Grouping::new(number(1.0), 1))),
+            token(TokenType::Star, "*"),
+            Box::new(Expression::Unary(Unary::new(
+                token(TokenType::Minus, "-"),
+                variable("x"),
+            ))),
+        ));
+        let copy = expr.clone();
+
+        assert_eq!(AstPrinter::print(&expr), "(* (group 1) (- x))");
+        assert_eq!(AstPrinter::print(&copy), AstPrinter::print(&expr));
+    }
+
+    #[test]
+    fn test_rpn_printer() {
+        let expr = Expression::Binary(Binary::new(
+            Box::new(Expression::Grouping(Grouping::new(
+                Box::new(Expression::Binary(Binary::new(
+                    number(1.0),
+                    token(TokenType::Plus, "+"),
+                    number(2.0),
+                ))),
+                1,
+            ))),
+            token(TokenType::Star, "*"),
+            Box::new(Expression::Grouping(Grouping::new(
+                Box::new(Expression::Binary(Binary::new(
+                    number(4.0),
+                    token(TokenType::Minus, "-"),
+                    number(3.0),
+                ))),
+                1,
+            ))),
+        ));
+        assert_eq!(RpnPrinter::print(&expr), "1 2 + 4 3 - *");
+
+        let negated = Expression::Unary(Unary::new(token(TokenType::Minus, "-"), number(5.0)));
+        assert_eq!(RpnPrinter::print(&negated), "5 ~");
+
+        let call = Expression::Call(Call::new(
+            variable("f"),
+            token(TokenType::RightParen, ")"),
+            vec![*number(1.0), *variable("x")],
+        ));
+        assert_eq!(RpnPrinter::print(&call), "f 1 x call/2");
+    }
+
+    #[test]
+    fn test_json_printer() {
+        let expr = Expression::Binary(Binary::new(
+            number(1.0),
+            token(TokenType::Plus, "+"),
+            Box::new(Expression::Literal(Literal::new(
+                LiteralValue::Str("a\"b".to_string()),
+                1,
+            ))),
+        ));
+        assert_eq!(
+            JsonPrinter::print(&expr),
+            r#"{"type":"Binary","operator":"+","left":{"type":"Literal","value":1},"right":{"type":"Literal","value":"a\"b"}}"#
+        );
+    }
+
+    #[test]
+    fn test_json_non_finite_numbers() {
+        let overflowing = format!("1{}", "0".repeat(400));
+        let (tokens, _) = Scanner::new(overflowing).scan_all();
+        let expr = parser::parse_expression(tokens).unwrap();
+        assert_eq!(
+            JsonPrinter::print(&expr),
+            r#"{"type":"Literal","value":null}"#
+        );
+
+        for n in [f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+            let expr = Expression::Literal(Literal::new(LiteralValue::Number(n), 1));
+            assert_eq!(
+                JsonPrinter::print(&expr),
+                r#"{"type":"Literal","value":null}"#
+            );
+        }
+    }
+
+    #[test]
+    fn test_dot_printer() {
+        let expr = Expression::Binary(Binary::new(
+            number(1.0),
+            token(TokenType::Plus, "+"),
+            number(2.0),
+        ));
+        let dot = DotPrinter::print(&expr);
+
+        assert!(dot.starts_with("digraph AST {"));
+        assert!(dot.contains("n0 [label=\"+\"];"));
+        assert!(dot.contains("n1 [label=\"1\"];"));
+        assert!(dot.contains("n2 [label=\"2\"];"));
+        assert!(dot.contains("n0 -> n1;"));
+        assert!(dot.contains("n0 -> n2;"));
+
+        let string = Expression::Literal(Literal::new(LiteralValue::Str("hi".to_string()), 1));
+        assert!(DotPrinter::print(&string).contains(r#"n0 [label="\"hi\""];"#));
+    }
+
+    #[test]
+    fn test_print_array() {
+        let expr = Expression::Array(Array::new(
+            vec![*number(1.0), *number(2.0), *number(3.0)],
+            1,
+        ));
+        assert_eq!(AstPrinter::print(&expr), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn test_print_index() {
+        let expr = Expression::Index(Index::new(
+            variable("a"),
+            number(0.0),
+            token(TokenType::RightBracket, "]"),
+        ));
+        assert_eq!(AstPrinter::print(&expr), "(index a 0)");
+    }
+
+    #[test]
+    fn test_print_function() {
+        let expr = Expression::Function(Function::new(
+            vec![identifier("a"), identifier("b")],
+            Vec::new(),
+            1,
+        ));
+        assert_eq!(AstPrinter::print(&expr), "(fun (a b))");
+    }
+
+    #[test]
+    fn test_print_map() {
+        let key = Expression::Literal(Literal::new(LiteralValue::Str("a".to_string()), 1));
+        let expr = Expression::Map(Map::new(
+            vec![(key, *number(1.0)), (*number(2.0), *variable("x"))],
+            1,
+        ));
+        assert_eq!(AstPrinter::print(&expr), "{\"a\": 1, 2: x}");
+    }
+
+    #[test]
+    fn test_print_many_variants() {
+        let source =
+            "a.b = f(x ? [1, y[0]] : {\"k\": -2}, this.z, super.m, fun (p) {}) or !(c and d)";
+        let (tokens, _) = Scanner::new(source.to_string()).scan_all();
+        let expr = parser::parse_expression(tokens).unwrap();
+
+        assert_eq!(
+            AstPrinter::print(&expr),
+            r#"(.= a b (or (call f (?: x [1, (index y 0)] {"k": (- 2)}) (. this z) (super m) (fun (p))) (! (group (and c d)))))"#
+        );
+    }
 }
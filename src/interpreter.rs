@@ -0,0 +1,624 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    io::{self, Write},
+    rc::Rc,
+};
+
+use crate::{
+    callable,
+    environment::Environment,
+    expr::{
+        Array, Assign, Binary, Call, Conditional, ExprVisitor, Expression, Function, Get, Grouping,
+        Index, Literal, Logical, Map, Set, Super, This, Unary, Variable, Visitable as _,
+    },
+    scanner::{Token, TokenType},
+    stmt::{self, Stmt, StmtVisitor, Visitable as _},
+    value::Value,
+};
+
+#[derive(Debug, Clone)]
+pub struct RuntimeError {
+    pub token: Token,
+    pub message: String,
+}
+
+impl RuntimeError {
+    pub fn new(token: Token, message: String) -> Self {
+        Self { token, message }
+    }
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n[line {}]", self.message, self.token.line())
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    !matches!(value, Value::Nil | Value::Bool(false))
+}
+
+// Values of different types are never equal; numbers follow IEEE, so NaN != NaN.
+fn is_equal(a: &Value, b: &Value) -> bool {
+    a == b
+}
+
+// Nodes without a token of their own report errors against a stand-in at their line.
+fn stand_in(line: usize, lexeme: &str) -> Token {
+    Token::new(TokenType::Identifier, lexeme.to_string(), line, None)
+}
+
+fn number_operands(op: &Token, left: &Value, right: &Value) -> Result<(f64, f64), RuntimeError> {
+    match (left, right) {
+        (Value::Number(a), Value::Number(b)) => Ok((*a, *b)),
+        _ => Err(RuntimeError::new(
+            op.clone(),
+            "operands must be numbers".to_string(),
+        )),
+    }
+}
+
+pub struct Interpreter {
+    globals: Rc<RefCell<Environment>>,
+    environment: Rc<RefCell<Environment>>,
+    output: Box<dyn Write>,
+}
+
+impl Interpreter {
+    pub fn new() -> Self {
+        Interpreter::with_output(io::stdout())
+    }
+
+    pub fn with_output(output: impl Write + 'static) -> Self {
+        let globals = Rc::new(RefCell::new(Environment::new()));
+
+        let clock = callable::clock();
+        globals
+            .borrow_mut()
+            .define(clock.name(), Value::Callable(Rc::new(clock)));
+
+        Self {
+            environment: Rc::clone(&globals),
+            globals,
+            output: Box::new(output),
+        }
+    }
+
+    pub fn globals(&self) -> Rc<RefCell<Environment>> {
+        Rc::clone(&self.globals)
+    }
+
+    pub fn interpret(&mut self, statements: &[Stmt]) -> Result<(), RuntimeError> {
+        for stmt in statements {
+            self.execute(stmt)?;
+        }
+        Ok(())
+    }
+
+    pub fn execute(&mut self, stmt: &Stmt) -> Result<(), RuntimeError> {
+        stmt.accept(self)
+    }
+
+    pub fn execute_block(
+        &mut self,
+        statements: &[Stmt],
+        environment: Environment,
+    ) -> Result<(), RuntimeError> {
+        let previous = std::mem::replace(&mut self.environment, Rc::new(RefCell::new(environment)));
+        let result = self.interpret(statements);
+        self.environment = previous;
+        result
+    }
+
+    pub fn evaluate(&mut self, expr: &Expression) -> Result<Value, RuntimeError> {
+        expr.accept(self)
+    }
+
+    fn unsupported<T>(&self, token: Token, what: &str) -> Result<T, RuntimeError> {
+        Err(RuntimeError::new(
+            token,
+            format!("{} is not supported", what),
+        ))
+    }
+
+    fn unsupported_at<T>(&self, line: usize, lexeme: &str, what: &str) -> Result<T, RuntimeError> {
+        self.unsupported(stand_in(line, lexeme), what)
+    }
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Interpreter::new()
+    }
+}
+
+impl ExprVisitor for Interpreter {
+    type Output = Result<Value, RuntimeError>;
+
+    fn visit_binary(&mut self, expr: &Binary) -> Self::Output {
+        let left = self.evaluate(&expr.left)?;
+        let right = self.evaluate(&expr.right)?;
+
+        match expr.op.token_type {
+            TokenType::Comma => Ok(right),
+            TokenType::EqualEqual => Ok(Value::Bool(is_equal(&left, &right))),
+            TokenType::BangEqual => Ok(Value::Bool(!is_equal(&left, &right))),
+            TokenType::Plus => match (left, right) {
+                (Value::Number(a), Value::Number(b)) => Ok(Value::Number(a + b)),
+                (Value::Str(a), Value::Str(b)) => Ok(Value::Str(a + &b)),
+                _ => Err(RuntimeError::new(
+                    expr.op.clone(),
+                    "operands must be two numbers or two strings".to_string(),
+                )),
+            },
+            TokenType::Greater => {
+                let (a, b) = number_operands(&expr.op, &left, &right)?;
+                Ok(Value::Bool(a > b))
+            }
+            TokenType::GreaterEqual => {
+                let (a, b) = number_operands(&expr.op, &left, &right)?;
+                Ok(Value::Bool(a >= b))
+            }
+            TokenType::Less => {
+                let (a, b) = number_operands(&expr.op, &left, &right)?;
+                Ok(Value::Bool(a < b))
+            }
+            TokenType::LessEqual => {
+                let (a, b) = number_operands(&expr.op, &left, &right)?;
+                Ok(Value::Bool(a <= b))
+            }
+            TokenType::Minus => {
+                let (a, b) = number_operands(&expr.op, &left, &right)?;
+                Ok(Value::Number(a - b))
+            }
+            TokenType::Star => {
+                let (a, b) = number_operands(&expr.op, &left, &right)?;
+                Ok(Value::Number(a * b))
+            }
+            TokenType::Slash => {
+                let (a, b) = number_operands(&expr.op, &left, &right)?;
+                if b == 0.0 {
+                    return Err(RuntimeError::new(
+                        expr.op.clone(),
+                        "division by zero".to_string(),
+                    ));
+                }
+                Ok(Value::Number(a / b))
+            }
+            TokenType::Percent => {
+                let (a, b) = number_operands(&expr.op, &left, &right)?;
+                if b == 0.0 {
+                    return Err(RuntimeError::new(
+                        expr.op.clone(),
+                        "modulo by zero".to_string(),
+                    ));
+                }
+                Ok(Value::Number(a % b))
+            }
+            TokenType::StarStar => {
+                let (a, b) = number_operands(&expr.op, &left, &right)?;
+                Ok(Value::Number(a.powf(b)))
+            }
+            _ => self.unsupported(expr.op.clone(), &format!("operator '{}'", expr.op)),
+        }
+    }
+
+    fn visit_grouping(&mut self, expr: &Grouping) -> Self::Output {
+        self.evaluate(&expr.expr)
+    }
+
+    fn visit_literal(&mut self, expr: &Literal) -> Self::Output {
+        Ok(Value::from(&expr.value))
+    }
+
+    fn visit_unary(&mut self, expr: &Unary) -> Self::Output {
+        let right = self.evaluate(&expr.right)?;
+
+        match expr.op.token_type {
+            TokenType::Bang => Ok(Value::Bool(!is_truthy(&right))),
+            TokenType::Minus => match right {
+                Value::Number(n) => Ok(Value::Number(-n)),
+                _ => Err(RuntimeError::new(
+                    expr.op.clone(),
+                    "operand must be a number".to_string(),
+                )),
+            },
+            _ => self.unsupported(expr.op.clone(), &format!("operator '{}'", expr.op)),
+        }
+    }
+
+    fn visit_variable(&mut self, expr: &Variable) -> Self::Output {
+        self.environment.borrow().get(&expr.name)
+    }
+
+    fn visit_assign(&mut self, expr: &Assign) -> Self::Output {
+        let value = self.evaluate(&expr.value)?;
+        self.environment
+            .borrow_mut()
+            .assign(&expr.name, value.clone())?;
+        Ok(value)
+    }
+
+    fn visit_logical(&mut self, expr: &Logical) -> Self::Output {
+        let left = self.evaluate(&expr.left)?;
+
+        if expr.op.token_type == TokenType::Or {
+            if is_truthy(&left) {
+                return Ok(left);
+            }
+        } else if !is_truthy(&left) {
+            return Ok(left);
+        }
+
+        self.evaluate(&expr.right)
+    }
+
+    fn visit_conditional(&mut self, expr: &Conditional) -> Self::Output {
+        if is_truthy(&self.evaluate(&expr.condition)?) {
+            self.evaluate(&expr.then_branch)
+        } else {
+            self.evaluate(&expr.else_branch)
+        }
+    }
+
+    fn visit_call(&mut self, expr: &Call) -> Self::Output {
+        let callee = self.evaluate(&expr.callee)?;
+
+        let mut arguments = Vec::with_capacity(expr.args.len());
+        for arg in &expr.args {
+            arguments.push(self.evaluate(arg)?);
+        }
+
+        let Value::Callable(function) = callee else {
+            return Err(RuntimeError::new(
+                expr.paren.clone(),
+                "can only call functions and classes".to_string(),
+            ));
+        };
+
+        if arguments.len() != function.arity() {
+            return Err(RuntimeError::new(
+                expr.paren.clone(),
+                format!(
+                    "expected {} arguments but got {}",
+                    function.arity(),
+                    arguments.len()
+                ),
+            ));
+        }
+
+        function.call(self, &arguments)
+    }
+
+    fn visit_get(&mut self, expr: &Get) -> Self::Output {
+        self.unsupported(expr.name.clone(), "property access")
+    }
+
+    fn visit_set(&mut self, expr: &Set) -> Self::Output {
+        self.unsupported(expr.name.clone(), "property assignment")
+    }
+
+    fn visit_this(&mut self, expr: &This) -> Self::Output {
+        self.unsupported(expr.keyword.clone(), "'this'")
+    }
+
+    fn visit_super(&mut self, expr: &Super) -> Self::Output {
+        self.unsupported(expr.keyword.clone(), "'super'")
+    }
+
+    fn visit_function(&mut self, expr: &Function) -> Self::Output {
+        self.unsupported_at(expr.line, "fun", "anonymous function")
+    }
+
+    fn visit_array(&mut self, expr: &Array) -> Self::Output {
+        self.unsupported_at(expr.line, "[", "array")
+    }
+
+    fn visit_index(&mut self, expr: &Index) -> Self::Output {
+        self.unsupported(expr.bracket.clone(), "indexing")
+    }
+
+    fn visit_map(&mut self, expr: &Map) -> Self::Output {
+        self.unsupported_at(expr.line, "{", "map")
+    }
+}
+
+impl StmtVisitor for Interpreter {
+    type Output = Result<(), RuntimeError>;
+
+    fn visit_block_stmt(&mut self, stmt: &stmt::Block) -> Self::Output {
+        let environment = Environment::with_enclosing(Rc::clone(&self.environment));
+        self.execute_block(&stmt.statements, environment)
+    }
+
+    fn visit_break_stmt(&mut self, stmt: &stmt::Break) -> Self::Output {
+        self.unsupported(stmt.keyword.clone(), "'break'")
+    }
+
+    fn visit_class_stmt(&mut self, stmt: &stmt::Class) -> Self::Output {
+        self.unsupported(stmt.name.clone(), "class declaration")
+    }
+
+    fn visit_const_stmt(&mut self, stmt: &stmt::Const) -> Self::Output {
+        let value = self.evaluate(&stmt.initializer)?;
+        self.environment
+            .borrow_mut()
+            .define(&stmt.name.to_string(), value);
+        Ok(())
+    }
+
+    fn visit_continue_stmt(&mut self, stmt: &stmt::Continue) -> Self::Output {
+        self.unsupported(stmt.keyword.clone(), "'continue'")
+    }
+
+    fn visit_expression_stmt(&mut self, stmt: &stmt::ExpressionStmt) -> Self::Output {
+        self.evaluate(&stmt.expr)?;
+        Ok(())
+    }
+
+    fn visit_function_stmt(&mut self, stmt: &stmt::Function) -> Self::Output {
+        self.unsupported(stmt.name.clone(), "function declaration")
+    }
+
+    fn visit_if_stmt(&mut self, stmt: &stmt::If) -> Self::Output {
+        if is_truthy(&self.evaluate(&stmt.condition)?) {
+            self.execute(&stmt.then_branch)
+        } else if let Some(else_branch) = &stmt.else_branch {
+            self.execute(else_branch)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn visit_print_stmt(&mut self, stmt: &stmt::Print) -> Self::Output {
+        let value = self.evaluate(&stmt.expr)?;
+        writeln!(self.output, "{}", value).map_err(|error| {
+            RuntimeError::new(
+                stmt.keyword.clone(),
+                format!("failed to write output: {}", error),
+            )
+        })
+    }
+
+    fn visit_return_stmt(&mut self, stmt: &stmt::Return) -> Self::Output {
+        self.unsupported(stmt.keyword.clone(), "'return'")
+    }
+
+    fn visit_switch_stmt(&mut self, stmt: &stmt::Switch) -> Self::Output {
+        self.unsupported_at(stmt.discriminant.line(), "switch", "'switch'")
+    }
+
+    fn visit_var_stmt(&mut self, stmt: &stmt::Var) -> Self::Output {
+        let value = match &stmt.initializer {
+            Some(initializer) => self.evaluate(initializer)?,
+            None => Value::Nil,
+        };
+        self.environment
+            .borrow_mut()
+            .define(&stmt.name.to_string(), value);
+        Ok(())
+    }
+
+    fn visit_while_stmt(&mut self, stmt: &stmt::While) -> Self::Output {
+        while is_truthy(&self.evaluate(&stmt.condition)?) {
+            self.execute(&stmt.body)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser, scanner::Scanner};
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct SharedOutput(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn evaluate(source: &str) -> Result<Value, RuntimeError> {
+        let (tokens, _) = Scanner::new(source.to_string()).scan_all();
+        let expr = parser::parse_expression(tokens).unwrap();
+        Interpreter::with_output(io::sink()).evaluate(&expr)
+    }
+
+    fn run(source: &str) -> Result<String, RuntimeError> {
+        let (tokens, _) = Scanner::new(source.to_string()).scan_all();
+        let statements = parser::parse(tokens).unwrap();
+        let output = SharedOutput::default();
+        Interpreter::with_output(output.clone()).interpret(&statements)?;
+        Ok(String::from_utf8(output.0.take()).unwrap())
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(evaluate("1 + 2 * 3").unwrap(), Value::Number(7.0));
+        assert_eq!(evaluate("(1 + 2) * 3").unwrap(), Value::Number(9.0));
+        assert_eq!(evaluate("10 - 4 / 2").unwrap(), Value::Number(8.0));
+        assert_eq!(evaluate("7 % 4").unwrap(), Value::Number(3.0));
+        assert_eq!(evaluate("2 ** 3 ** 2").unwrap(), Value::Number(512.0));
+    }
+
+    #[test]
+    fn test_unary_and_grouping() {
+        assert_eq!(evaluate("-(-5)").unwrap(), Value::Number(5.0));
+        assert_eq!(evaluate("!true").unwrap(), Value::Bool(false));
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(evaluate("\"hi\"").unwrap(), Value::Str("hi".to_string()));
+        assert_eq!(evaluate("nil").unwrap(), Value::Nil);
+        assert_eq!(evaluate("false").unwrap(), Value::Bool(false));
+    }
+
+    #[test]
+    fn test_runtime_error_display() {
+        let error = evaluate("1 +\n\n-\"a\"").unwrap_err();
+        assert_eq!(error.to_string(), "operand must be a number\n[line 3]");
+    }
+
+    #[test]
+    fn test_equality() {
+        assert_eq!(evaluate("1 == 1").unwrap(), Value::Bool(true));
+        assert_eq!(evaluate("\"a\" != \"b\"").unwrap(), Value::Bool(true));
+        assert_eq!(evaluate("nil == nil").unwrap(), Value::Bool(true));
+        assert_eq!(evaluate("1 == \"1\"").unwrap(), Value::Bool(false));
+        assert_eq!(evaluate("nil == false").unwrap(), Value::Bool(false));
+        assert_eq!(evaluate("true != 1").unwrap(), Value::Bool(true));
+
+        let nan = Value::Number(f64::NAN);
+        assert!(!is_equal(&nan, &nan));
+    }
+
+    #[test]
+    fn test_plus() {
+        assert_eq!(evaluate("1 + 2 == 3").unwrap(), Value::Bool(true));
+        assert_eq!(
+            evaluate("\"a\" + \"b\"").unwrap(),
+            Value::Str("ab".to_string())
+        );
+        assert_eq!(
+            evaluate("\"a\" + \"b\" == \"ab\"").unwrap(),
+            Value::Bool(true)
+        );
+
+        let error = evaluate("1\n+ \"a\"").unwrap_err();
+        assert_eq!(error.message, "operands must be two numbers or two strings");
+        assert_eq!(error.token.line(), 2);
+    }
+
+    #[test]
+    fn test_comparison() {
+        assert_eq!(evaluate("2 > 1").unwrap(), Value::Bool(true));
+        assert_eq!(evaluate("1 > 1").unwrap(), Value::Bool(false));
+        assert_eq!(evaluate("1 >= 1").unwrap(), Value::Bool(true));
+        assert_eq!(evaluate("1 < 2").unwrap(), Value::Bool(true));
+        assert_eq!(evaluate("2 < 1").unwrap(), Value::Bool(false));
+        assert_eq!(evaluate("2 <= 1").unwrap(), Value::Bool(false));
+
+        let error = evaluate("1 < \"2\"").unwrap_err();
+        assert_eq!(error.message, "operands must be numbers");
+    }
+
+    #[test]
+    fn test_unary_operators() {
+        assert_eq!(evaluate("-5").unwrap(), Value::Number(-5.0));
+        assert_eq!(evaluate("!nil").unwrap(), Value::Bool(true));
+        assert_eq!(evaluate("!0").unwrap(), Value::Bool(false));
+        assert_eq!(evaluate("!\"\"").unwrap(), Value::Bool(false));
+
+        let error = evaluate("-\"x\"").unwrap_err();
+        assert_eq!(error.message, "operand must be a number");
+        assert_eq!(error.token.token_type, TokenType::Minus);
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+        assert_eq!(evaluate("4 / 2 == 2").unwrap(), Value::Bool(true));
+
+        let error = evaluate("1 / 0").unwrap_err();
+        assert_eq!(error.message, "division by zero");
+
+        let error = evaluate("1 % (2 - 2)").unwrap_err();
+        assert_eq!(error.message, "modulo by zero");
+    }
+
+    #[test]
+    fn test_variables() {
+        assert_eq!(run("var x = 10; print x;").unwrap(), "10\n");
+        assert_eq!(run("var x; print x;").unwrap(), "nil\n");
+        assert_eq!(run("var x = 1; x = x + 1; print x;").unwrap(), "2\n");
+
+        let error = run("print y;").unwrap_err();
+        assert_eq!(error.message, "undefined variable 'y'");
+    }
+
+    #[test]
+    fn test_block_scope() {
+        let source = "
+            var a = \"outer\";
+            {
+                var a = \"inner\";
+                print a;
+            }
+            print a;
+        ";
+        assert_eq!(run(source).unwrap(), "inner\nouter\n");
+
+        let source = "var a = 1; { a = 2; var b = 3; } print a;";
+        assert_eq!(run(source).unwrap(), "2\n");
+
+        let error = run("{ var b = 3; } print b;").unwrap_err();
+        assert_eq!(error.message, "undefined variable 'b'");
+    }
+
+    #[test]
+    fn test_clock() {
+        let Value::Number(seconds) = evaluate("clock()").unwrap() else {
+            panic!("expected clock() to return a number");
+        };
+        assert!(seconds > 0.0);
+
+        let error = evaluate("clock(1)").unwrap_err();
+        assert_eq!(error.message, "expected 0 arguments but got 1");
+
+        let error = evaluate("\"clock\"()").unwrap_err();
+        assert_eq!(error.message, "can only call functions and classes");
+
+        assert_eq!(run("print clock;").unwrap(), "<native fn clock>\n");
+        assert_eq!(evaluate("clock == clock").unwrap(), Value::Bool(true));
+    }
+
+    #[test]
+    fn test_print_reports_write_errors() {
+        struct ClosedPipe;
+
+        impl Write for ClosedPipe {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                Err(io::Error::from(io::ErrorKind::BrokenPipe))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let (tokens, _) = Scanner::new("\nprint 1;".to_string()).scan_all();
+        let statements = parser::parse(tokens).unwrap();
+        let error = Interpreter::with_output(ClosedPipe)
+            .interpret(&statements)
+            .unwrap_err();
+
+        assert!(error.message.starts_with("failed to write output"));
+        assert_eq!(error.token.token_type, TokenType::Print);
+        assert_eq!(error.token.line(), 2);
+    }
+
+    #[test]
+    fn test_globals_visible_in_blocks() {
+        let (tokens, _) = Scanner::new("{ { print answer; } }".to_string()).scan_all();
+        let statements = parser::parse(tokens).unwrap();
+
+        let output = SharedOutput::default();
+        let mut interpreter = Interpreter::with_output(output.clone());
+        interpreter
+            .globals()
+            .borrow_mut()
+            .define("answer", Value::Number(42.0));
+        interpreter.interpret(&statements).unwrap();
+
+        assert_eq!(String::from_utf8(output.0.take()).unwrap(), "42\n");
+    }
+}
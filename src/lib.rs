@@ -0,0 +1,9 @@
+pub mod callable;
+pub mod environment;
+pub mod expr;
+pub mod interpreter;
+pub mod lox;
+pub mod parser;
+pub mod scanner;
+pub mod stmt;
+pub mod value;
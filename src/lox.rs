@@ -3,24 +3,28 @@ use std::{
     process::exit,
 };
 
-use crate::scanner::{self};
+use crate::{interpreter::Interpreter, parser, scanner};
 
-pub fn run_file(file: String) -> () {
+pub fn run_file(file: String) {
     let contents = std::fs::read_to_string(&file).unwrap_or_else(|err| {
         eprintln!("Could not read file {}: {}", file, err);
         exit(74);
     });
+
+    run(contents, &mut Interpreter::new());
 }
 
-pub fn run_repl() -> () {
+pub fn run_repl() {
     let stdin = io::stdin();
     let mut handle = stdin.lock();
 
     let stdout = io::stdout();
     let mut out = stdout.lock();
 
+    let mut interpreter = Interpreter::new();
+
     loop {
-        out.write("> ".as_bytes()).unwrap();
+        out.write_all("> ".as_bytes()).unwrap();
         out.flush().unwrap();
 
         let mut line = String::new();
@@ -30,21 +34,28 @@ pub fn run_repl() -> () {
             break;
         }
 
-        run(line);
+        run(line, &mut interpreter);
     }
 }
 
-pub fn run(code: String) -> () {
-    let mut scanner = scanner::Scanner::new(code);
-    let tokens = scanner.scan_tokens();
+pub fn run(code: String, interpreter: &mut Interpreter) {
+    let (tokens, errors) = scanner::Scanner::new(code).scan_all();
+    if !errors.is_empty() {
+        for error in errors {
+            eprintln!("{}", error);
+        }
+        return;
+    }
 
-    for token in tokens {
-        match token {
-            scanner::ScannerResult::Token(token) => {
-                println!("{:?}", token);
+    match parser::parse(tokens) {
+        Ok(statements) => {
+            if let Err(error) = interpreter.interpret(&statements) {
+                eprintln!("{}", error);
             }
-            scanner::ScannerResult::Error(error) => {
-                println!("{:?}", error);
+        }
+        Err(errors) => {
+            for error in errors {
+                eprintln!("{}", error);
             }
         }
     }
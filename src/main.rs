@@ -1,11 +1,6 @@
-mod expr;
-mod lox;
-mod parser;
-mod scanner;
-
 use std::process::exit;
 
-use crate::lox::{run_file, run_repl};
+use ilox::lox::{run_file, run_repl};
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
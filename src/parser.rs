@@ -1,104 +1,923 @@
+use std::fmt;
+
 use crate::{
-    expr::{Binary, Expression, Literal, Unary},
-    scanner::{Token, TokenType},
+    expr::{
+        self, Array, Assign, Binary, Call, Conditional, Expression, Get, Grouping, Index, Literal,
+        Logical, Map, Set, Super, This, Unary, Variable,
+    },
+    scanner::{LiteralValue, Token, TokenType},
+    stmt::{
+        Block, Break, Class, Const, Continue, ExpressionStmt, Function, If, Print, Return, Stmt,
+        Switch, Var, While,
+    },
 };
 
-struct Parser {
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    pub token: Token,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(token: Token, message: String) -> Self {
+        Self { token, message }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.token.token_type == TokenType::Eof {
+            write!(
+                f,
+                "[line {}] Error at end: {}",
+                self.token.line(),
+                self.message
+            )
+        } else {
+            write!(
+                f,
+                "[line {}] Error at '{}': {}",
+                self.token.line(),
+                self.token,
+                self.message
+            )
+        }
+    }
+}
+
+pub fn parse(tokens: Vec<Token>) -> Result<Vec<Stmt>, Vec<ParseError>> {
+    let (statements, errors) = Parser::new(tokens).parse();
+
+    if errors.is_empty() {
+        Ok(statements)
+    } else {
+        Err(errors)
+    }
+}
+
+pub fn parse_expression(tokens: Vec<Token>) -> Result<Expression, ParseError> {
+    Parser::new(tokens).parse_expression()
+}
+
+const MAX_ARGUMENTS: usize = 255;
+
+fn compound_operator(token: &Token) -> Option<Token> {
+    let (token_type, lexeme) = match token.token_type {
+        TokenType::PlusEqual => (TokenType::Plus, "+"),
+        TokenType::MinusEqual => (TokenType::Minus, "-"),
+        TokenType::StarEqual => (TokenType::Star, "*"),
+        TokenType::SlashEqual => (TokenType::Slash, "/"),
+        _ => return None,
+    };
+
+    Some(Token::new(
+        token_type,
+        lexeme.to_string(),
+        token.line(),
+        None,
+    ))
+}
+
+pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    errors: Vec<ParseError>,
+    fold_negative_literals: bool,
 }
 
 impl Parser {
-    fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, current: 0 }
+    pub fn new(mut tokens: Vec<Token>) -> Self {
+        if tokens.last().is_none_or(|t| t.token_type != TokenType::Eof) {
+            tokens.push(Token::new(TokenType::Eof, "".to_string(), 0, None));
+        }
+        Self {
+            tokens,
+            current: 0,
+            errors: Vec::new(),
+            fold_negative_literals: false,
+        }
+    }
+
+    // Turns `-5` into `Literal(-5)` instead of `Unary(-, Literal(5))`.
+    pub fn with_fold_negative_literals(mut self, fold_negative_literals: bool) -> Self {
+        self.fold_negative_literals = fold_negative_literals;
+        self
     }
 
     fn next(&mut self) -> Option<Token> {
+        if self.is_at_end() {
+            return None;
+        }
         self.current += 1;
-        self.tokens.get(self.current - 1).cloned()
+        Some(self.previous())
     }
 
-    fn peek(&self) -> Option<&Token> {
-        self.tokens.get(self.current)
+    fn peek(&self) -> &Token {
+        &self.tokens[self.current]
     }
 
-    fn expression(&mut self) -> Expression {
-        return self.equality();
+    pub fn parse(&mut self) -> (Vec<Stmt>, Vec<ParseError>) {
+        let mut statements = Vec::new();
+
+        while !self.is_at_end() {
+            match self.declaration() {
+                Ok(stmt) => statements.push(stmt),
+                Err(error) => {
+                    self.errors.push(error);
+                    self.synchronize();
+                }
+            }
+        }
+
+        (statements, std::mem::take(&mut self.errors))
     }
 
-    fn equality(&mut self) -> Expression {
-        let mut expr = self.comparison();
-        while self.match_token(TokenType::Equal, TokenType::BangEqual) {
-            let op = self.next().unwrap();
-            let right = self.comparison();
-            expr = Expression::Binary(Binary::new(Box::new(expr), op, Box::new(right)));
+    pub fn parse_expression(&mut self) -> Result<Expression, ParseError> {
+        let expr = self.expression()?;
+        self.consume(TokenType::Eof, "Expected end of input")?;
+
+        match std::mem::take(&mut self.errors).into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(expr),
         }
-        expr
     }
 
-    fn comparison(&mut self) -> Expression {
-        let mut expr = self.term();
+    fn declaration(&mut self) -> Result<Stmt, ParseError> {
+        if self.matches(&[TokenType::Class]) {
+            return self.class_declaration();
+        }
+
+        if self.check(&[TokenType::Fun]) && self.check_next(TokenType::Identifier) {
+            self.next();
+            return Ok(Stmt::Function(self.function("function")?));
+        }
+
+        if self.matches(&[TokenType::Const]) {
+            return self.const_declaration();
+        }
+
+        if self.matches(&[TokenType::Var]) {
+            return self.var_declaration();
+        }
+
+        self.statement()
+    }
+
+    fn class_declaration(&mut self) -> Result<Stmt, ParseError> {
+        let name = self.consume(TokenType::Identifier, "Expected class name")?;
+
+        let superclass = if self.matches(&[TokenType::Less]) {
+            let name = self.consume(TokenType::Identifier, "Expected superclass name")?;
+            Some(Expression::Variable(Variable::new(name)))
+        } else {
+            None
+        };
+
+        self.consume(TokenType::LeftBrace, "Expected '{' before class body")?;
+
+        let mut methods = Vec::new();
+        while !self.check(&[TokenType::RightBrace]) && !self.is_at_end() {
+            methods.push(self.function("method")?);
+        }
+
+        self.consume(TokenType::RightBrace, "Expected '}' after class body")?;
+        Ok(Stmt::Class(Class::new(name, superclass, methods)))
+    }
+
+    fn function(&mut self, kind: &str) -> Result<Function, ParseError> {
+        let name = self.consume(TokenType::Identifier, &format!("Expected {} name", kind))?;
+        self.consume(
+            TokenType::LeftParen,
+            &format!("Expected '(' after {} name", kind),
+        )?;
+        let (params, body) = self.function_body(kind)?;
+
+        Ok(Function::new(name, params, body))
+    }
+
+    fn function_body(&mut self, kind: &str) -> Result<(Vec<Token>, Vec<Stmt>), ParseError> {
+        let mut params = Vec::new();
+        if !self.check(&[TokenType::RightParen]) {
+            loop {
+                params.push(self.consume(TokenType::Identifier, "Expected parameter name")?);
+                if !self.matches(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+        self.consume(TokenType::RightParen, "Expected ')' after parameters")?;
+
+        self.consume(
+            TokenType::LeftBrace,
+            &format!("Expected '{{' before {} body", kind),
+        )?;
+        let body = self.block()?;
+
+        Ok((params, body))
+    }
+
+    fn const_declaration(&mut self) -> Result<Stmt, ParseError> {
+        let name = self.consume(TokenType::Identifier, "Expected constant name")?;
+        self.consume(
+            TokenType::Equal,
+            "Expected '=' after constant name; constants must be initialized",
+        )?;
+        let initializer = self.expression()?;
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after constant declaration",
+        )?;
+
+        Ok(Stmt::Const(Const::new(name, initializer)))
+    }
+
+    fn var_declaration(&mut self) -> Result<Stmt, ParseError> {
+        let name = self.consume(TokenType::Identifier, "Expected variable name")?;
+
+        let initializer = if self.matches(&[TokenType::Equal]) {
+            Some(self.expression()?)
+        } else {
+            None
+        };
 
-        while self.match_token(TokenType::Greater, TokenType::GreaterEqual)
-            || self.match_token(TokenType::Less, TokenType::LessEqual)
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after variable declaration",
+        )?;
+        Ok(Stmt::Var(Var::new(name, initializer)))
+    }
+
+    fn statement(&mut self) -> Result<Stmt, ParseError> {
+        if self.matches(&[TokenType::Break]) {
+            return self.break_statement();
+        }
+
+        if self.matches(&[TokenType::Continue]) {
+            return self.continue_statement();
+        }
+
+        if self.matches(&[TokenType::For]) {
+            return self.for_statement();
+        }
+
+        if self.matches(&[TokenType::If]) {
+            return self.if_statement();
+        }
+
+        if self.matches(&[TokenType::Print]) {
+            return self.print_statement();
+        }
+
+        if self.matches(&[TokenType::Return]) {
+            return self.return_statement();
+        }
+
+        if self.matches(&[TokenType::Switch]) {
+            return self.switch_statement();
+        }
+
+        if self.matches(&[TokenType::While]) {
+            return self.while_statement();
+        }
+
+        if self.matches(&[TokenType::LeftBrace]) {
+            return Ok(Stmt::Block(Block::new(self.block()?)));
+        }
+
+        self.expression_statement()
+    }
+
+    fn return_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous();
+        let value = if self.check(&[TokenType::Semicolon]) {
+            None
+        } else {
+            Some(self.expression()?)
+        };
+
+        self.consume(TokenType::Semicolon, "Expected ';' after return value")?;
+        Ok(Stmt::Return(Return::new(keyword, value)))
+    }
+
+    fn switch_statement(&mut self) -> Result<Stmt, ParseError> {
+        self.consume(TokenType::LeftParen, "Expected '(' after 'switch'")?;
+        let discriminant = self.expression()?;
+        self.consume(TokenType::RightParen, "Expected ')' after switch value")?;
+        self.consume(TokenType::LeftBrace, "Expected '{' before switch body")?;
+
+        let mut cases = Vec::new();
+        let mut default = None;
+
+        while !self.check(&[TokenType::RightBrace]) && !self.is_at_end() {
+            if self.matches(&[TokenType::Case]) {
+                let value = self.expression()?;
+                self.consume(TokenType::Colon, "Expected ':' after case value")?;
+                cases.push((value, self.switch_arm()?));
+            } else if self.matches(&[TokenType::Default]) {
+                let keyword = self.previous();
+                self.consume(TokenType::Colon, "Expected ':' after 'default'")?;
+                let body = self.switch_arm()?;
+
+                if default.is_some() {
+                    self.errors.push(ParseError::new(
+                        keyword,
+                        "Switch can only have one default".to_string(),
+                    ));
+                } else {
+                    default = Some(body);
+                }
+            } else {
+                return Err(self.error("Expected 'case' or 'default' in switch body"));
+            }
+        }
+
+        self.consume(TokenType::RightBrace, "Expected '}' after switch body")?;
+        Ok(Stmt::Switch(Switch::new(discriminant, cases, default)))
+    }
+
+    fn switch_arm(&mut self) -> Result<Vec<Stmt>, ParseError> {
+        let mut statements = Vec::new();
+
+        while !self.check(&[TokenType::Case, TokenType::Default, TokenType::RightBrace])
+            && !self.is_at_end()
         {
-            let op = self.next().unwrap();
-            let right = self.term();
+            statements.push(self.declaration()?);
+        }
+        Ok(statements)
+    }
+
+    fn while_statement(&mut self) -> Result<Stmt, ParseError> {
+        self.consume(TokenType::LeftParen, "Expected '(' after 'while'")?;
+        let condition = self.expression()?;
+        self.consume(TokenType::RightParen, "Expected ')' after condition")?;
+        let body = Box::new(self.statement()?);
+
+        Ok(Stmt::While(While::new(condition, body)))
+    }
+
+    fn block(&mut self) -> Result<Vec<Stmt>, ParseError> {
+        let mut statements = Vec::new();
+
+        while !self.check(&[TokenType::RightBrace]) && !self.is_at_end() {
+            statements.push(self.declaration()?);
+        }
+
+        self.consume(TokenType::RightBrace, "Expected '}' after block")?;
+        Ok(statements)
+    }
+
+    fn break_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous();
+        self.consume(TokenType::Semicolon, "Expected ';' after 'break'")?;
+        Ok(Stmt::Break(Break::new(keyword)))
+    }
+
+    fn continue_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous();
+        self.consume(TokenType::Semicolon, "Expected ';' after 'continue'")?;
+        Ok(Stmt::Continue(Continue::new(keyword)))
+    }
+
+    fn for_statement(&mut self) -> Result<Stmt, ParseError> {
+        self.consume(TokenType::LeftParen, "Expected '(' after 'for'")?;
+
+        let initializer = if self.matches(&[TokenType::Semicolon]) {
+            Vec::new()
+        } else if self.matches(&[TokenType::Var]) {
+            self.for_var_declarations()?
+        } else {
+            vec![self.expression_statement()?]
+        };
+
+        let condition = if self.check(&[TokenType::Semicolon]) {
+            Expression::Literal(Literal::new(LiteralValue::Bool(true), self.peek().line()))
+        } else {
+            self.expression()?
+        };
+        self.consume(TokenType::Semicolon, "Expected ';' after loop condition")?;
+
+        let increment = if self.check(&[TokenType::RightParen]) {
+            None
+        } else {
+            Some(self.expression()?)
+        };
+        self.consume(TokenType::RightParen, "Expected ')' after for clauses")?;
+
+        let mut body = self.statement()?;
+
+        if let Some(increment) = increment {
+            body = Stmt::Block(Block::new(vec![
+                body,
+                Stmt::Expression(ExpressionStmt::new(increment)),
+            ]));
+        }
+
+        body = Stmt::While(While::new(condition, Box::new(body)));
+
+        if !initializer.is_empty() {
+            let mut statements = initializer;
+            statements.push(body);
+            body = Stmt::Block(Block::new(statements));
+        }
+
+        Ok(body)
+    }
+
+    fn for_var_declarations(&mut self) -> Result<Vec<Stmt>, ParseError> {
+        let mut declarations = Vec::new();
+
+        loop {
+            let name = self.consume(TokenType::Identifier, "Expected variable name")?;
+            let initializer = if self.matches(&[TokenType::Equal]) {
+                Some(self.assignment()?)
+            } else {
+                None
+            };
+            declarations.push(Stmt::Var(Var::new(name, initializer)));
+
+            if !self.matches(&[TokenType::Comma]) {
+                break;
+            }
+        }
+
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after variable declaration",
+        )?;
+        Ok(declarations)
+    }
+
+    fn if_statement(&mut self) -> Result<Stmt, ParseError> {
+        self.consume(TokenType::LeftParen, "Expected '(' after 'if'")?;
+        let condition = self.expression()?;
+        self.consume(TokenType::RightParen, "Expected ')' after if condition")?;
+
+        let then_branch = Box::new(self.statement()?);
+        let else_branch = if self.matches(&[TokenType::Else]) {
+            Some(Box::new(self.statement()?))
+        } else {
+            None
+        };
+
+        Ok(Stmt::If(If::new(condition, then_branch, else_branch)))
+    }
+
+    fn print_statement(&mut self) -> Result<Stmt, ParseError> {
+        let keyword = self.previous();
+        let value = self.expression()?;
+        self.consume(TokenType::Semicolon, "Expected ';' after value")?;
+        Ok(Stmt::Print(Print::new(keyword, value)))
+    }
+
+    fn expression_statement(&mut self) -> Result<Stmt, ParseError> {
+        let expr = self.expression()?;
+        self.consume(TokenType::Semicolon, "Expected ';' after expression")?;
+        Ok(Stmt::Expression(ExpressionStmt::new(expr)))
+    }
+
+    fn expression(&mut self) -> Result<Expression, ParseError> {
+        self.comma()
+    }
+
+    fn comma(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.assignment()?;
+
+        while self.matches(&[TokenType::Comma]) {
+            let op = self.previous();
+            let right = self.assignment()?;
             expr = Expression::Binary(Binary::new(Box::new(expr), op, Box::new(right)));
         }
-        expr
+        Ok(expr)
+    }
+
+    fn assignment(&mut self) -> Result<Expression, ParseError> {
+        let expr = self.conditional()?;
+
+        if self.matches(&[
+            TokenType::Equal,
+            TokenType::PlusEqual,
+            TokenType::MinusEqual,
+            TokenType::StarEqual,
+            TokenType::SlashEqual,
+        ]) {
+            let equals = self.previous();
+            let mut value = self.assignment()?;
+
+            // `a.b += 1` desugars to `a.b = a.b + 1`, so the object expression
+            // is evaluated twice.
+            if let Some(op) = compound_operator(&equals) {
+                value =
+                    Expression::Binary(Binary::new(Box::new(expr.clone()), op, Box::new(value)));
+            }
+
+            return match expr {
+                Expression::Variable(variable) => Ok(Expression::Assign(Assign::new(
+                    variable.name,
+                    Box::new(value),
+                ))),
+                Expression::Get(get) => Ok(Expression::Set(Set::new(
+                    get.object,
+                    get.name,
+                    Box::new(value),
+                ))),
+                _ => Err(ParseError::new(
+                    equals,
+                    "Invalid assignment target".to_string(),
+                )),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn conditional(&mut self) -> Result<Expression, ParseError> {
+        let condition = self.or()?;
+
+        if self.matches(&[TokenType::Question]) {
+            let then_branch = self.expression()?;
+            self.consume(
+                TokenType::Colon,
+                "Expected ':' after then branch of conditional expression",
+            )?;
+            let else_branch = self.conditional()?;
+            return Ok(Expression::Conditional(Conditional::new(
+                Box::new(condition),
+                Box::new(then_branch),
+                Box::new(else_branch),
+            )));
+        }
+
+        Ok(condition)
     }
 
-    fn term(&mut self) -> Expression {
-        let mut expr = self.factor();
+    fn or(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.and()?;
 
-        while self.match_token(TokenType::Minus, TokenType::Plus) {
-            let op = self.next().unwrap();
-            let right = self.factor();
+        while self.matches(&[TokenType::Or]) {
+            let op = self.previous();
+            let right = self.and()?;
+            expr = Expression::Logical(Logical::new(Box::new(expr), op, Box::new(right)));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.bitwise_or()?;
+
+        while self.matches(&[TokenType::And]) {
+            let op = self.previous();
+            let right = self.bitwise_or()?;
+            expr = Expression::Logical(Logical::new(Box::new(expr), op, Box::new(right)));
+        }
+        Ok(expr)
+    }
+
+    fn bitwise_or(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.bitwise_xor()?;
+
+        while self.matches(&[TokenType::Pipe]) {
+            let op = self.previous();
+            let right = self.bitwise_xor()?;
             expr = Expression::Binary(Binary::new(Box::new(expr), op, Box::new(right)));
         }
-        expr
+        Ok(expr)
     }
 
-    fn factor(&mut self) -> Expression {
-        let mut expr = self.unary();
+    fn bitwise_xor(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.bitwise_and()?;
 
-        while self.match_token(TokenType::Slash, TokenType::Star) {
-            let op = self.next().unwrap();
-            let right = self.unary();
+        while self.matches(&[TokenType::Caret]) {
+            let op = self.previous();
+            let right = self.bitwise_and()?;
             expr = Expression::Binary(Binary::new(Box::new(expr), op, Box::new(right)));
         }
-        expr
+        Ok(expr)
     }
 
-    fn unary(&mut self) -> Expression {
-        if self.match_token(TokenType::Minus, TokenType::Minus) {
-            let op = self.next().unwrap();
-            let right = self.unary();
-            Expression::Unary(Unary::new(op, Box::new(right)))
+    fn bitwise_and(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.equality()?;
+
+        while self.matches(&[TokenType::Ampersand]) {
+            let op = self.previous();
+            let right = self.equality()?;
+            expr = Expression::Binary(Binary::new(Box::new(expr), op, Box::new(right)));
+        }
+        Ok(expr)
+    }
+
+    fn equality(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.comparison()?;
+        while self.matches(&[TokenType::EqualEqual, TokenType::BangEqual]) {
+            let op = self.previous();
+            let right = self.comparison()?;
+            expr = Expression::Binary(Binary::new(Box::new(expr), op, Box::new(right)));
+        }
+        Ok(expr)
+    }
+
+    fn comparison(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.shift()?;
+
+        while self.matches(&[
+            TokenType::Greater,
+            TokenType::GreaterEqual,
+            TokenType::Less,
+            TokenType::LessEqual,
+        ]) {
+            let op = self.previous();
+            let right = self.shift()?;
+            expr = Expression::Binary(Binary::new(Box::new(expr), op, Box::new(right)));
+        }
+        Ok(expr)
+    }
+
+    fn shift(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.term()?;
+
+        while self.matches(&[TokenType::LessLess, TokenType::GreaterGreater]) {
+            let op = self.previous();
+            let right = self.term()?;
+            expr = Expression::Binary(Binary::new(Box::new(expr), op, Box::new(right)));
+        }
+        Ok(expr)
+    }
+
+    fn term(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.factor()?;
+
+        while self.matches(&[TokenType::Minus, TokenType::Plus]) {
+            let op = self.previous();
+            let right = self.factor()?;
+            expr = Expression::Binary(Binary::new(Box::new(expr), op, Box::new(right)));
+        }
+        Ok(expr)
+    }
+
+    fn factor(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.unary()?;
+
+        while self.matches(&[TokenType::Slash, TokenType::Star, TokenType::Percent]) {
+            let op = self.previous();
+            let right = self.unary()?;
+            expr = Expression::Binary(Binary::new(Box::new(expr), op, Box::new(right)));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expression, ParseError> {
+        if self.matches(&[TokenType::Bang, TokenType::Minus]) {
+            let op = self.previous();
+            let right = self.unary()?;
+
+            if self.fold_negative_literals
+                && op.token_type == TokenType::Minus
+                && let Expression::Literal(Literal {
+                    value: LiteralValue::Number(n),
+                    ..
+                }) = right
+            {
+                let value = LiteralValue::Number(-n);
+                return Ok(Expression::Literal(Literal::new(value, op.line())));
+            }
+
+            Ok(Expression::Unary(Unary::new(op, Box::new(right))))
+        } else if self.check(&[TokenType::Plus]) {
+            Err(self.error("unary '+' is not supported"))
         } else {
-            self.primary()
+            self.power()
+        }
+    }
+
+    fn power(&mut self) -> Result<Expression, ParseError> {
+        let expr = self.call()?;
+
+        if self.matches(&[TokenType::StarStar]) {
+            let op = self.previous();
+            let right = self.unary()?;
+            return Ok(Expression::Binary(Binary::new(
+                Box::new(expr),
+                op,
+                Box::new(right),
+            )));
+        }
+        Ok(expr)
+    }
+
+    fn call(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.primary()?;
+
+        loop {
+            if self.matches(&[TokenType::LeftParen]) {
+                expr = self.finish_call(expr)?;
+            } else if self.matches(&[TokenType::LeftBracket]) {
+                let open = self.previous();
+                let index = self.expression()?;
+                let bracket = self.consume_closing(TokenType::RightBracket, &open)?;
+                expr = Expression::Index(Index::new(Box::new(expr), Box::new(index), bracket));
+            } else if self.matches(&[TokenType::Dot]) {
+                let name =
+                    self.consume(TokenType::Identifier, "Expected property name after '.'")?;
+                expr = Expression::Get(Get::new(Box::new(expr), name));
+            } else {
+                break;
+            }
         }
+        Ok(expr)
     }
 
-    fn primary(&mut self) -> Expression {
-        if self.match_token(TokenType::LeftParen, TokenType::RightParen) {
-            let _ = self.next().unwrap();
-            let expr = self.expression();
-            let _ = self.next().unwrap();
-            expr
-        } else if self.match_token(TokenType::Number, TokenType::String) {
-            let value = self.next().unwrap().value.clone().unwrap();
-            Expression::Literal(Literal::new(value))
+    // Accepts `f()`, `f(a, b)` and `f(a, b,)`. A comma must follow an argument, so `f(,)`
+    // is still an error.
+    fn finish_call(&mut self, callee: Expression) -> Result<Expression, ParseError> {
+        let open = self.previous();
+        let mut args = Vec::new();
+
+        while !self.check(&[TokenType::RightParen]) {
+            if args.len() >= MAX_ARGUMENTS {
+                let error =
+                    self.error(&format!("Can't have more than {} arguments", MAX_ARGUMENTS));
+                self.errors.push(error);
+            }
+            args.push(self.assignment()?);
+            if !self.matches(&[TokenType::Comma]) {
+                break;
+            }
+        }
+
+        let paren = self.consume_closing(TokenType::RightParen, &open)?;
+        Ok(Expression::Call(Call::new(Box::new(callee), paren, args)))
+    }
+
+    fn primary(&mut self) -> Result<Expression, ParseError> {
+        if self.matches(&[TokenType::LeftParen]) {
+            let open = self.previous();
+            let expr = self.expression()?;
+            self.consume_closing(TokenType::RightParen, &open)?;
+            Ok(Expression::Grouping(Grouping::new(
+                Box::new(expr),
+                open.line(),
+            )))
+        } else if self.matches(&[TokenType::Number, TokenType::String]) {
+            let token = self.previous();
+            let value = match token.value.clone() {
+                Some(value) => value,
+                None => return Err(ParseError::new(token, "Literal has no value".to_string())),
+            };
+            Ok(Expression::Literal(Literal::new(value, token.line())))
+        } else if self.matches(&[TokenType::True, TokenType::False]) {
+            let token = self.previous();
+            let value = LiteralValue::Bool(token.token_type == TokenType::True);
+            Ok(Expression::Literal(Literal::new(value, token.line())))
+        } else if self.matches(&[TokenType::Nil]) {
+            let value = LiteralValue::Nil;
+            Ok(Expression::Literal(Literal::new(
+                value,
+                self.previous().line(),
+            )))
+        } else if self.matches(&[TokenType::LeftBracket]) {
+            let open = self.previous();
+            let mut elements = Vec::new();
+
+            while !self.check(&[TokenType::RightBracket]) && !self.is_at_end() {
+                elements.push(self.assignment()?);
+                if !self.matches(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+
+            self.consume_closing(TokenType::RightBracket, &open)?;
+            Ok(Expression::Array(Array::new(elements, open.line())))
+        } else if self.matches(&[TokenType::LeftBrace]) {
+            // Only reached where an expression is expected, so `{}` here is an
+            // empty map; at the start of a statement it is an empty block.
+            self.map()
+        } else if self.matches(&[TokenType::Fun]) {
+            let keyword = self.previous();
+            self.consume(TokenType::LeftParen, "Expected '(' after 'fun'")?;
+            let (params, body) = self.function_body("function")?;
+            let function = expr::Function::new(params, body, keyword.line());
+            Ok(Expression::Function(function))
+        } else if self.matches(&[TokenType::Super]) {
+            let keyword = self.previous();
+            self.consume(TokenType::Dot, "Expected '.' after 'super'")?;
+            let method = self.consume(TokenType::Identifier, "Expected superclass method name")?;
+            Ok(Expression::Super(Super::new(keyword, method)))
+        } else if self.matches(&[TokenType::This]) {
+            Ok(Expression::This(This::new(self.previous())))
+        } else if self.matches(&[TokenType::Identifier]) {
+            Ok(Expression::Variable(Variable::new(self.previous())))
         } else {
-            panic!("Unexpected token");
+            Err(self.error("Expected expression"))
+        }
+    }
+
+    fn map(&mut self) -> Result<Expression, ParseError> {
+        let open = self.previous();
+        let mut entries = Vec::new();
+
+        while !self.check(&[TokenType::RightBrace]) && !self.is_at_end() {
+            if !self.check(&[TokenType::String, TokenType::Number]) {
+                return Err(self.error("Expected string or number as map key"));
+            }
+            let key = self.primary()?;
+            self.consume(TokenType::Colon, "Expected ':' after map key")?;
+            let value = self.assignment()?;
+            entries.push((key, value));
+
+            if !self.matches(&[TokenType::Comma]) {
+                break;
+            }
+        }
+
+        self.consume_closing(TokenType::RightBrace, &open)?;
+        Ok(Expression::Map(Map::new(entries, open.line())))
+    }
+
+    fn consume(&mut self, ttype: TokenType, msg: &str) -> Result<Token, ParseError> {
+        if self.check(&[ttype]) {
+            self.next();
+            return Ok(self.previous());
+        }
+
+        Err(self.error(msg))
+    }
+
+    fn consume_closing(&mut self, ttype: TokenType, open: &Token) -> Result<Token, ParseError> {
+        let closing = match ttype {
+            TokenType::RightParen => ")",
+            TokenType::RightBracket => "]",
+            TokenType::RightBrace => "}",
+            _ => unreachable!("not a closing delimiter: {:?}", ttype),
+        };
+
+        self.consume(
+            ttype,
+            &format!(
+                "Expected '{}' to close '{}' from line {}",
+                closing,
+                open,
+                open.line()
+            ),
+        )
+    }
+
+    fn error(&self, message: &str) -> ParseError {
+        ParseError::new(self.peek().clone(), message.to_string())
+    }
+
+    fn synchronize(&mut self) {
+        self.next();
+
+        while !self.is_at_end() {
+            if self.previous().token_type == TokenType::Semicolon {
+                return;
+            }
+
+            if self.check(&[
+                TokenType::Class,
+                TokenType::Fun,
+                TokenType::Var,
+                TokenType::Const,
+                TokenType::For,
+                TokenType::If,
+                TokenType::While,
+                TokenType::Switch,
+                TokenType::Print,
+                TokenType::Return,
+                TokenType::Break,
+                TokenType::Continue,
+            ]) {
+                return;
+            }
+
+            self.next();
         }
     }
 
-    fn match_token(&mut self, type1: TokenType, type2: TokenType) -> bool {
-        self.peek()
-            .map(|t| t.token_type == type1 || t.token_type == type2)
-            .unwrap_or(false)
+    fn is_at_end(&self) -> bool {
+        self.peek().token_type == TokenType::Eof
+    }
+
+    fn previous(&self) -> Token {
+        self.tokens[self.current - 1].clone()
+    }
+
+    fn check(&self, types: &[TokenType]) -> bool {
+        types.contains(&self.peek().token_type)
+    }
+
+    fn check_next(&self, ttype: TokenType) -> bool {
+        self.tokens
+            .get(self.current + 1)
+            .is_some_and(|t| t.token_type == ttype)
+    }
+
+    fn matches(&mut self, types: &[TokenType]) -> bool {
+        if self.check(types) {
+            self.next();
+            return true;
+        }
+
+        false
     }
 }
 
@@ -106,11 +925,26 @@ impl Parser {
 mod tests {
     use crate::{
         expr::AstPrinter,
-        scanner::{LiteralValue, Scanner, ScannerResult, TokenType},
+        scanner::{Scanner, ScannerResult},
     };
 
     use super::*;
 
+    fn print_expression(source: &str) -> String {
+        let (tokens, _) = Scanner::new(source.to_string()).scan_all();
+        AstPrinter::print(&parse_expression(tokens).unwrap())
+    }
+
+    fn parse_statement(source: &str) -> Result<Stmt, ParseError> {
+        let (tokens, _) = Scanner::new(source.to_string()).scan_all();
+        Parser::new(tokens).declaration()
+    }
+
+    fn parse_errors(source: &str) -> Vec<ParseError> {
+        let (tokens, _) = Scanner::new(source.to_string()).scan_all();
+        parse(tokens).unwrap_err()
+    }
+
     #[test]
     fn test_parser() {
         let program = "123 + 45 * 67 + 4";
@@ -125,7 +959,769 @@ mod tests {
             .collect();
 
         let mut parser = Parser::new(filtered);
-        let expr = parser.expression();
+        let expr = parser.expression().unwrap();
         assert_eq!(AstPrinter::print(&expr), "(+ (+ 123 (* 45 67)) 4)");
     }
+
+    #[test]
+    fn test_boolean_and_nil_literals() {
+        assert_eq!(print_expression("true"), "true");
+        assert_eq!(print_expression("false"), "false");
+        assert_eq!(print_expression("nil"), "nil");
+    }
+
+    #[test]
+    fn test_equality_uses_equal_equal() {
+        assert_eq!(print_expression("1 == 2"), "(== 1 2)");
+        assert_eq!(print_expression("1 != 2"), "(!= 1 2)");
+        assert_eq!(
+            parse_errors("1 = 2")[0].message,
+            "Invalid assignment target"
+        );
+    }
+
+    #[test]
+    fn test_unary_operators() {
+        assert_eq!(print_expression("!true"), "(! true)");
+        assert_eq!(print_expression("-5"), "(- 5)");
+        assert_eq!(print_expression("!-5"), "(! (- 5))");
+    }
+
+    #[test]
+    fn test_comparison_operators() {
+        assert_eq!(
+            print_expression("1 < 2 >= 3 > 4 <= 5"),
+            "(<= (> (>= (< 1 2) 3) 4) 5)"
+        );
+    }
+
+    #[test]
+    fn test_check_and_matches() {
+        let (tokens, _) = Scanner::new("< +".to_string()).scan_all();
+        let mut parser = Parser::new(tokens);
+
+        assert!(parser.check(&[TokenType::Greater, TokenType::Less]));
+        assert!(!parser.matches(&[TokenType::Plus, TokenType::Minus]));
+        assert!(parser.matches(&[TokenType::Greater, TokenType::Less]));
+        assert!(parser.matches(&[TokenType::Plus, TokenType::Minus]));
+        assert!(parser.check(&[TokenType::Eof]));
+    }
+
+    #[test]
+    fn test_reports_multiple_errors() {
+        let (tokens, _) = Scanner::new("1 + ; 2; 3 *".to_string()).scan_all();
+        let errors = parse(tokens).unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].token.token_type, TokenType::Semicolon);
+        assert_eq!(errors[1].token.token_type, TokenType::Eof);
+    }
+
+    #[test]
+    fn test_parse_result() {
+        let (tokens, _) = Scanner::new("1 + 2; (3);".to_string()).scan_all();
+        let statements = parse(tokens).unwrap();
+        let printed: Vec<String> = statements
+            .iter()
+            .map(|stmt| match stmt {
+                Stmt::Expression(stmt) => AstPrinter::print(&stmt.expr),
+                _ => panic!("expected an expression statement"),
+            })
+            .collect();
+        assert_eq!(printed, vec!["(+ 1 2)", "(group 3)"]);
+
+        let (tokens, _) = Scanner::new("1 + )".to_string()).scan_all();
+        let errors = parse(tokens).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].token.token_type, TokenType::RightParen);
+        assert_eq!(errors[0].message, "Expected expression");
+    }
+
+    #[test]
+    fn test_unclosed_paren_does_not_panic() {
+        let (tokens, _) = Scanner::new("(1 + 2".to_string()).scan_all();
+        let errors = parse(tokens).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].token.token_type, TokenType::Eof);
+        assert_eq!(errors[0].message, "Expected ')' to close '(' from line 1");
+    }
+
+    #[test]
+    fn test_grouping() {
+        assert_eq!(print_expression("(1 + 2)"), "(group (+ 1 2))");
+        assert_eq!(print_expression("(1 + 2) * 3"), "(* (group (+ 1 2)) 3)");
+    }
+
+    #[test]
+    fn test_variable_reference() {
+        assert_eq!(print_expression("foo + 1"), "(+ foo 1)");
+    }
+
+    #[test]
+    fn test_assignment() {
+        assert_eq!(print_expression("x = 1 + 2"), "(= x (+ 1 2))");
+
+        let errors = parse_errors("1 = 2");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].token.token_type, TokenType::Equal);
+        assert_eq!(errors[0].message, "Invalid assignment target");
+    }
+
+    #[test]
+    fn test_logical_or() {
+        assert_eq!(print_expression("a or b or c"), "(or (or a b) c)");
+    }
+
+    #[test]
+    fn test_logical_and_binds_tighter_than_or() {
+        assert_eq!(print_expression("a or b and c"), "(or a (and b c))");
+        assert_eq!(print_expression("a and b == c"), "(and a (== b c))");
+    }
+
+    #[test]
+    fn test_comma_operator() {
+        assert_eq!(print_expression("1, 2, 3"), "(, (, 1 2) 3)");
+        assert_eq!(print_expression("a = 1, b = 2"), "(, (= a 1) (= b 2))");
+    }
+
+    #[test]
+    fn test_conditional() {
+        assert_eq!(print_expression("a ? b : c ? d : e"), "(?: a b (?: c d e))");
+        assert_eq!(print_expression("x = a ? 1 : 2"), "(= x (?: a 1 2))");
+
+        let errors = parse_errors("a ? b");
+        assert_eq!(errors[0].token.token_type, TokenType::Eof);
+        assert_eq!(
+            errors[0].message,
+            "Expected ':' after then branch of conditional expression"
+        );
+    }
+
+    #[test]
+    fn test_call() {
+        assert_eq!(print_expression("f(1, 2)"), "(call f 1 2)");
+        assert_eq!(print_expression("g()()"), "(call (call g))");
+        assert_eq!(print_expression("f(a, b), c"), "(, (call f a b) c)");
+    }
+
+    #[test]
+    fn test_property_access() {
+        assert_eq!(print_expression("a.b.c"), "(. (. a b) c)");
+        assert_eq!(print_expression("a.b().c"), "(. (call (. a b)) c)");
+
+        let errors = parse_errors("a.1");
+        assert_eq!(errors[0].token.token_type, TokenType::Number);
+        assert_eq!(errors[0].message, "Expected property name after '.'");
+    }
+
+    #[test]
+    fn test_property_assignment() {
+        assert_eq!(print_expression("a.b = 3"), "(.= a b 3)");
+        assert_eq!(print_expression("a.b().c = 3"), "(.= (call (. a b)) c 3)");
+
+        let errors = parse_errors("a.b() = 3");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "Invalid assignment target");
+    }
+
+    #[test]
+    fn test_print_statement() {
+        let Stmt::Print(print) = parse_statement("print 1 + 2;").unwrap() else {
+            panic!("expected a print statement");
+        };
+        assert_eq!(AstPrinter::print(&print.expr), "(+ 1 2)");
+
+        let error = parse_statement("print 1 + 2").unwrap_err();
+        assert_eq!(error.token.token_type, TokenType::Eof);
+        assert_eq!(error.message, "Expected ';' after value");
+    }
+
+    #[test]
+    fn test_expression_statement() {
+        let Stmt::Expression(stmt) = parse_statement("foo();").unwrap() else {
+            panic!("expected an expression statement");
+        };
+        assert_eq!(AstPrinter::print(&stmt.expr), "(call foo)");
+
+        let error = parse_statement("foo()").unwrap_err();
+        assert_eq!(error.token.token_type, TokenType::Eof);
+        assert_eq!(error.message, "Expected ';' after expression");
+    }
+
+    #[test]
+    fn test_var_declaration() {
+        let Stmt::Var(var) = parse_statement("var x = 1 + 2;").unwrap() else {
+            panic!("expected a var declaration");
+        };
+        assert_eq!(var.name.to_string(), "x");
+        assert_eq!(
+            AstPrinter::print(var.initializer.as_ref().unwrap()),
+            "(+ 1 2)"
+        );
+
+        let Stmt::Var(var) = parse_statement("var y;").unwrap() else {
+            panic!("expected a var declaration");
+        };
+        assert_eq!(var.name.to_string(), "y");
+        assert!(var.initializer.is_none());
+
+        let error = parse_statement("var 5 = 1;").unwrap_err();
+        assert_eq!(error.token.token_type, TokenType::Number);
+        assert_eq!(error.message, "Expected variable name");
+    }
+
+    #[test]
+    fn test_block_statement() {
+        let Stmt::Block(block) = parse_statement("{ var x = 1; print x; }").unwrap() else {
+            panic!("expected a block");
+        };
+        assert_eq!(block.statements.len(), 2);
+        assert!(matches!(block.statements[0], Stmt::Var(_)));
+        assert!(matches!(block.statements[1], Stmt::Print(_)));
+
+        let error = parse_statement("{ print 1;").unwrap_err();
+        assert_eq!(error.token.token_type, TokenType::Eof);
+        assert_eq!(error.message, "Expected '}' after block");
+    }
+
+    #[test]
+    fn test_if_statement() {
+        let Stmt::If(stmt) = parse_statement("if (a) print 1;").unwrap() else {
+            panic!("expected an if statement");
+        };
+        assert_eq!(AstPrinter::print(&stmt.condition), "a");
+        assert!(matches!(*stmt.then_branch, Stmt::Print(_)));
+        assert!(stmt.else_branch.is_none());
+
+        let Stmt::If(stmt) = parse_statement("if (a) print 1; else { print 2; }").unwrap() else {
+            panic!("expected an if statement");
+        };
+        assert!(matches!(stmt.else_branch.as_deref(), Some(Stmt::Block(_))));
+
+        let error = parse_statement("if a print 1;").unwrap_err();
+        assert_eq!(error.message, "Expected '(' after 'if'");
+
+        let error = parse_statement("if (a print 1;").unwrap_err();
+        assert_eq!(error.message, "Expected ')' after if condition");
+    }
+
+    #[test]
+    fn test_dangling_else_binds_to_nearest_if() {
+        let Stmt::If(outer) = parse_statement("if (a) if (b) print 1; else print 2;").unwrap()
+        else {
+            panic!("expected an if statement");
+        };
+        assert!(outer.else_branch.is_none());
+
+        let Stmt::If(inner) = *outer.then_branch else {
+            panic!("expected a nested if statement");
+        };
+        assert!(matches!(inner.else_branch.as_deref(), Some(Stmt::Print(_))));
+    }
+
+    #[test]
+    fn test_while_statement() {
+        let Stmt::While(stmt) = parse_statement("while (x < 10) x = x + 1;").unwrap() else {
+            panic!("expected a while statement");
+        };
+        assert_eq!(AstPrinter::print(&stmt.condition), "(< x 10)");
+        let Stmt::Expression(body) = *stmt.body else {
+            panic!("expected an expression body");
+        };
+        assert_eq!(AstPrinter::print(&body.expr), "(= x (+ x 1))");
+
+        let Stmt::While(stmt) = parse_statement("while (true) { print 1; }").unwrap() else {
+            panic!("expected a while statement");
+        };
+        assert!(matches!(*stmt.body, Stmt::Block(_)));
+
+        let error = parse_statement("while (x < 10 x = x + 1;").unwrap_err();
+        assert_eq!(error.message, "Expected ')' after condition");
+    }
+
+    #[test]
+    fn test_for_desugars_to_while() {
+        let stmt = parse_statement("for (var i = 0; i < 10; i = i + 1) print i;").unwrap();
+        let Stmt::Block(outer) = stmt else {
+            panic!("expected the initializer block");
+        };
+        let [Stmt::Var(var), Stmt::While(stmt)] = outer.statements.as_slice() else {
+            panic!("expected an initializer followed by a while loop");
+        };
+        assert_eq!(var.name.to_string(), "i");
+        assert_eq!(AstPrinter::print(&stmt.condition), "(< i 10)");
+
+        let Stmt::Block(body) = &*stmt.body else {
+            panic!("expected the loop body block");
+        };
+        let [Stmt::Print(print), Stmt::Expression(increment)] = body.statements.as_slice() else {
+            panic!("expected the body followed by the increment");
+        };
+        assert_eq!(AstPrinter::print(&print.expr), "i");
+        assert_eq!(AstPrinter::print(&increment.expr), "(= i (+ i 1))");
+    }
+
+    #[test]
+    fn test_for_with_empty_clauses() {
+        let Stmt::While(stmt) = parse_statement("for (;;) print 1;").unwrap() else {
+            panic!("expected a bare while loop");
+        };
+        assert_eq!(AstPrinter::print(&stmt.condition), "true");
+        assert!(matches!(*stmt.body, Stmt::Print(_)));
+    }
+
+    #[test]
+    fn test_function_declaration() {
+        let Stmt::Function(function) = parse_statement("fun f() {}").unwrap() else {
+            panic!("expected a function declaration");
+        };
+        assert_eq!(function.name.to_string(), "f");
+        assert!(function.params.is_empty());
+        assert!(function.body.is_empty());
+
+        let Stmt::Function(function) = parse_statement("fun add(a, b) { print a + b; }").unwrap()
+        else {
+            panic!("expected a function declaration");
+        };
+        let params: Vec<String> = function.params.iter().map(|p| p.to_string()).collect();
+        assert_eq!(params, vec!["a", "b"]);
+        assert_eq!(function.body.len(), 1);
+    }
+
+    #[test]
+    fn test_malformed_parameter_list() {
+        let error = parse_statement("fun f(a b) {}").unwrap_err();
+        assert_eq!(error.message, "Expected ')' after parameters");
+
+        let error = parse_statement("fun f(1) {}").unwrap_err();
+        assert_eq!(error.message, "Expected parameter name");
+
+        let error = parse_statement("fun f(a,) {}").unwrap_err();
+        assert_eq!(error.message, "Expected parameter name");
+    }
+
+    #[test]
+    fn test_return_statement() {
+        let Stmt::Return(stmt) = parse_statement("return;").unwrap() else {
+            panic!("expected a return statement");
+        };
+        assert_eq!(stmt.keyword.token_type, TokenType::Return);
+        assert!(stmt.value.is_none());
+
+        let Stmt::Return(stmt) = parse_statement("return a + 1;").unwrap() else {
+            panic!("expected a return statement");
+        };
+        assert_eq!(AstPrinter::print(stmt.value.as_ref().unwrap()), "(+ a 1)");
+
+        let error = parse_statement("return 1").unwrap_err();
+        assert_eq!(error.message, "Expected ';' after return value");
+    }
+
+    #[test]
+    fn test_class_declaration() {
+        let Stmt::Class(class) =
+            parse_statement("class Point { init(x) { print x; } norm() { return 1; } }").unwrap()
+        else {
+            panic!("expected a class declaration");
+        };
+        assert_eq!(class.name.to_string(), "Point");
+        assert!(class.superclass.is_none());
+        let methods: Vec<String> = class.methods.iter().map(|m| m.name.to_string()).collect();
+        assert_eq!(methods, vec!["init", "norm"]);
+    }
+
+    #[test]
+    fn test_class_with_superclass() {
+        let Stmt::Class(class) = parse_statement("class B < A {}").unwrap() else {
+            panic!("expected a class declaration");
+        };
+        assert_eq!(AstPrinter::print(class.superclass.as_ref().unwrap()), "A");
+        assert!(class.methods.is_empty());
+
+        let error = parse_statement("class B < {}").unwrap_err();
+        assert_eq!(error.message, "Expected superclass name");
+    }
+
+    #[test]
+    fn test_this_expression() {
+        assert_eq!(print_expression("this.x"), "(. this x)");
+        assert_eq!(print_expression("this"), "this");
+    }
+
+    #[test]
+    fn test_super_expression() {
+        assert_eq!(print_expression("super.foo"), "(super foo)");
+        assert_eq!(print_expression("super.foo(1)"), "(call (super foo) 1)");
+
+        let error = parse_statement("super;").unwrap_err();
+        assert_eq!(error.token.token_type, TokenType::Semicolon);
+        assert_eq!(error.message, "Expected '.' after 'super'");
+    }
+
+    #[test]
+    fn test_parse_program() {
+        let source = "var x = 1;\nfun f(a) { return a + x; }\nprint f(2);";
+        let (tokens, _) = Scanner::new(source.to_string()).scan_all();
+        let statements = parse(tokens).unwrap();
+
+        assert_eq!(statements.len(), 3);
+        assert!(matches!(statements[0], Stmt::Var(_)));
+        assert!(matches!(statements[1], Stmt::Function(_)));
+        assert!(matches!(statements[2], Stmt::Print(_)));
+    }
+
+    #[test]
+    fn test_argument_limit() {
+        let args: Vec<String> = (0..256).map(|i| format!("a{}", i)).collect();
+        let source = format!("f({}); 1 +;", args.join(", "));
+        let errors = parse_errors(&source);
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].token.to_string(), "a255");
+        assert_eq!(errors[0].message, "Can't have more than 255 arguments");
+        assert_eq!(errors[1].message, "Expected expression");
+
+        let args: Vec<String> = (0..255).map(|i| format!("a{}", i)).collect();
+        assert!(parse_statement(&format!("f({});", args.join(", "))).is_ok());
+    }
+
+    #[test]
+    fn test_trailing_tokens_are_rejected() {
+        let (tokens, _) = Scanner::new("1 + 2 3".to_string()).scan_all();
+        let error = parse_expression(tokens).unwrap_err();
+        assert_eq!(error.token.to_string(), "3");
+        assert_eq!(error.message, "Expected end of input");
+
+        let errors = parse_errors("1 + 2 3;");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].token.to_string(), "3");
+    }
+
+    #[test]
+    fn test_assignment_is_right_associative() {
+        assert_eq!(print_expression("a = b = 1"), "(= a (= b 1))");
+        assert_eq!(print_expression("a.x = b = 1"), "(.= a x (= b 1))");
+    }
+
+    #[test]
+    fn test_unmatched_paren_reports_opening_line() {
+        let errors = parse_errors("print (1 +\n2\n;");
+        assert_eq!(errors[0].token.line(), 3);
+        assert_eq!(errors[0].message, "Expected ')' to close '(' from line 1");
+
+        let errors = parse_errors("f(1,\n2;");
+        assert_eq!(errors[0].message, "Expected ')' to close '(' from line 1");
+    }
+
+    #[test]
+    fn test_break_statement() {
+        let Stmt::Break(stmt) = parse_statement("break;").unwrap() else {
+            panic!("expected a break statement");
+        };
+        assert_eq!(stmt.keyword.token_type, TokenType::Break);
+
+        let error = parse_statement("break").unwrap_err();
+        assert_eq!(error.message, "Expected ';' after 'break'");
+    }
+
+    #[test]
+    fn test_continue_statement() {
+        let Stmt::Continue(stmt) = parse_statement("continue;").unwrap() else {
+            panic!("expected a continue statement");
+        };
+        assert_eq!(stmt.keyword.token_type, TokenType::Continue);
+
+        let error = parse_statement("continue").unwrap_err();
+        assert_eq!(error.message, "Expected ';' after 'continue'");
+    }
+
+    #[test]
+    fn test_anonymous_function() {
+        let Stmt::Var(var) = parse_statement("var f = fun (a, b) { return a + b; };").unwrap()
+        else {
+            panic!("expected a var declaration");
+        };
+        let Some(Expression::Function(function)) = &var.initializer else {
+            panic!("expected an anonymous function initializer");
+        };
+        assert_eq!(function.params.len(), 2);
+        assert!(matches!(function.body[0], Stmt::Return(_)));
+        assert_eq!(
+            print_expression("fun (a, b) { return a + b; }"),
+            "(fun (a b))"
+        );
+
+        let Stmt::Expression(stmt) = parse_statement("fun () {}();").unwrap() else {
+            panic!("expected an expression statement");
+        };
+        assert_eq!(AstPrinter::print(&stmt.expr), "(call (fun ()))");
+    }
+
+    #[test]
+    fn test_switch_statement() {
+        let source = "switch (x) { case 1: print 1; case 2: print 2; print 3; default: print 0; }";
+        let Stmt::Switch(stmt) = parse_statement(source).unwrap() else {
+            panic!("expected a switch statement");
+        };
+        assert_eq!(AstPrinter::print(&stmt.discriminant), "x");
+
+        let cases: Vec<(String, usize)> = stmt
+            .cases
+            .iter()
+            .map(|(value, body)| (AstPrinter::print(value), body.len()))
+            .collect();
+        assert_eq!(cases, vec![("1".to_string(), 1), ("2".to_string(), 2)]);
+        assert_eq!(stmt.default.map(|body| body.len()), Some(1));
+    }
+
+    #[test]
+    fn test_switch_with_duplicate_default() {
+        let errors = parse_errors("switch (x) { default: print 1; default: print 2; }");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].token.token_type, TokenType::Default);
+        assert_eq!(errors[0].message, "Switch can only have one default");
+    }
+
+    #[test]
+    fn test_const_declaration() {
+        let Stmt::Const(stmt) = parse_statement("const x = 1 + 2;").unwrap() else {
+            panic!("expected a const declaration");
+        };
+        assert_eq!(stmt.name.to_string(), "x");
+        assert_eq!(AstPrinter::print(&stmt.initializer), "(+ 1 2)");
+
+        let error = parse_statement("const x;").unwrap_err();
+        assert_eq!(error.token.token_type, TokenType::Semicolon);
+        assert_eq!(
+            error.message,
+            "Expected '=' after constant name; constants must be initialized"
+        );
+    }
+
+    #[test]
+    fn test_compound_assignment_desugars() {
+        assert_eq!(print_expression("x += 1"), "(= x (+ x 1))");
+        assert_eq!(print_expression("x -= 1"), "(= x (- x 1))");
+        assert_eq!(print_expression("x *= 2 + 3"), "(= x (* x (+ 2 3)))");
+        assert_eq!(print_expression("x /= y = 2"), "(= x (/ x (= y 2)))");
+        assert_eq!(print_expression("a.b += 1"), "(.= a b (+ (. a b) 1))");
+
+        let errors = parse_errors("1 += 2;");
+        assert_eq!(errors[0].message, "Invalid assignment target");
+    }
+
+    #[test]
+    fn test_power_is_right_associative() {
+        assert_eq!(print_expression("2 ** 3 ** 2"), "(** 2 (** 3 2))");
+        assert_eq!(print_expression("2 * 3 ** 2"), "(* 2 (** 3 2))");
+        assert_eq!(print_expression("-2 ** 2"), "(- (** 2 2))");
+        assert_eq!(print_expression("2 ** -1"), "(** 2 (- 1))");
+    }
+
+    #[test]
+    fn test_bitwise_precedence() {
+        assert_eq!(print_expression("1 | 2 & 3"), "(| 1 (& 2 3))");
+        assert_eq!(print_expression("1 | 2 ^ 3"), "(| 1 (^ 2 3))");
+        assert_eq!(print_expression("1 ^ 2 & 3"), "(^ 1 (& 2 3))");
+        assert_eq!(print_expression("1 & 2 == 3"), "(& 1 (== 2 3))");
+        assert_eq!(print_expression("a and 1 | 2"), "(and a (| 1 2))");
+    }
+
+    #[test]
+    fn test_shift_precedence() {
+        assert_eq!(print_expression("1 + 2 << 3"), "(<< (+ 1 2) 3)");
+        assert_eq!(print_expression("1 << 2 >> 3"), "(>> (<< 1 2) 3)");
+        assert_eq!(print_expression("1 < 2 << 3"), "(< 1 (<< 2 3))");
+    }
+
+    #[test]
+    fn test_modulo_precedence() {
+        assert_eq!(print_expression("10 % 3 * 2"), "(* (% 10 3) 2)");
+        assert_eq!(print_expression("8 / 4 % 3"), "(% (/ 8 4) 3)");
+        assert_eq!(print_expression("1 + 10 % 3"), "(+ 1 (% 10 3))");
+    }
+
+    #[test]
+    fn test_array_literals() {
+        assert_eq!(print_expression("[]"), "[]");
+        assert_eq!(print_expression("[1, 2 + 3, x]"), "[1, (+ 2 3), x]");
+        assert_eq!(print_expression("[1, 2, 3,]"), "[1, 2, 3]");
+
+        let errors = parse_errors("[1, 2;");
+        assert_eq!(errors[0].message, "Expected ']' to close '[' from line 1");
+
+        let errors = parse_errors("[,];");
+        assert_eq!(errors[0].message, "Expected expression");
+    }
+
+    #[test]
+    fn test_index_expressions() {
+        assert_eq!(print_expression("a[0]"), "(index a 0)");
+        assert_eq!(print_expression("a[0][1]"), "(index (index a 0) 1)");
+        assert_eq!(print_expression("[1, 2][i + 1]"), "(index [1, 2] (+ i 1))");
+
+        let errors = parse_errors("a[0;");
+        assert_eq!(errors[0].message, "Expected ']' to close '[' from line 1");
+    }
+
+    #[test]
+    fn test_map_literals() {
+        assert_eq!(
+            print_expression("{\"a\": 1, 2: x + 1}"),
+            "{\"a\": 1, 2: (+ x 1)}"
+        );
+        assert_eq!(print_expression("{}"), "{}");
+
+        let Stmt::Var(var) = parse_statement("var m = {};").unwrap() else {
+            panic!("expected a var declaration");
+        };
+        assert!(matches!(var.initializer, Some(Expression::Map(_))));
+        assert!(matches!(parse_statement("{}").unwrap(), Stmt::Block(_)));
+
+        let errors = parse_errors("var m = {a: 1};");
+        assert_eq!(errors[0].message, "Expected string or number as map key");
+    }
+
+    #[test]
+    fn test_for_with_multiple_declarations() {
+        let stmt = parse_statement("for (var i = 0, j = 10; i < j; i = i + 1) print i;").unwrap();
+        let Stmt::Block(outer) = stmt else {
+            panic!("expected the initializer block");
+        };
+        let [Stmt::Var(i), Stmt::Var(j), Stmt::While(_)] = outer.statements.as_slice() else {
+            panic!("expected two declarations followed by a while loop");
+        };
+        assert_eq!(i.name.to_string(), "i");
+        assert_eq!(AstPrinter::print(i.initializer.as_ref().unwrap()), "0");
+        assert_eq!(j.name.to_string(), "j");
+        assert_eq!(AstPrinter::print(j.initializer.as_ref().unwrap()), "10");
+    }
+
+    #[test]
+    fn test_recovers_after_broken_statement() {
+        let source = "print 1;\nvar = 2;\nprint 3;";
+        let (tokens, _) = Scanner::new(source.to_string()).scan_all();
+        let (statements, errors) = Parser::new(tokens).parse();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].token.line(), 2);
+
+        let printed: Vec<String> = statements
+            .iter()
+            .map(|stmt| match stmt {
+                Stmt::Print(print) => AstPrinter::print(&print.expr),
+                _ => panic!("expected a print statement"),
+            })
+            .collect();
+        assert_eq!(printed, vec!["1", "3"]);
+    }
+
+    #[test]
+    fn test_chained_call_get_and_index() {
+        assert_eq!(
+            print_expression("obj.method(1).field[2]()"),
+            "(call (index (. (call (. obj method) 1) field) 2))"
+        );
+        assert_eq!(
+            print_expression("a[0].b(c)[1]"),
+            "(index (call (. (index a 0) b) c) 1)"
+        );
+    }
+
+    #[test]
+    fn test_call_argument_forms() {
+        assert_eq!(print_expression("f()"), "(call f)");
+        assert_eq!(print_expression("f(1, 2)"), "(call f 1 2)");
+        assert_eq!(print_expression("f(1, 2,)"), "(call f 1 2)");
+
+        let errors = parse_errors("f(,);");
+        assert_eq!(errors[0].message, "Expected expression");
+
+        let errors = parse_errors("f(1,,);");
+        assert_eq!(errors[0].message, "Expected expression");
+    }
+
+    #[test]
+    fn test_unary_plus_is_rejected() {
+        let errors = parse_errors("+5;");
+        assert_eq!(errors[0].message, "unary '+' is not supported");
+        assert_eq!(errors[0].token.token_type, TokenType::Plus);
+
+        let errors = parse_errors("1 * +5;");
+        assert_eq!(errors[0].message, "unary '+' is not supported");
+    }
+
+    #[test]
+    fn test_fold_negative_literals() {
+        let folded = |source: &str| {
+            let (tokens, _) = Scanner::new(source.to_string()).scan_all();
+            let expr = Parser::new(tokens)
+                .with_fold_negative_literals(true)
+                .parse_expression()
+                .unwrap();
+            AstPrinter::print(&expr)
+        };
+
+        assert_eq!(print_expression("-5"), "(- 5)");
+        assert_eq!(folded("-5"), "-5");
+
+        assert_eq!(print_expression("1 - -2.5"), "(- 1 (- 2.5))");
+        assert_eq!(folded("1 - -2.5"), "(- 1 -2.5)");
+
+        assert_eq!(folded("-x"), "(- x)");
+        assert_eq!(folded("!5"), "(! 5)");
+        assert_eq!(folded("-2 ** 2"), "(- (** 2 2))");
+    }
+
+    #[test]
+    fn test_parse_error_display() {
+        let errors = parse_errors("var x = 1;\nvar 2 = 3;");
+        assert_eq!(
+            errors[0].to_string(),
+            "[line 2] Error at '2': Expected variable name"
+        );
+
+        let errors = parse_errors("print 1");
+        assert_eq!(
+            errors[0].to_string(),
+            "[line 1] Error at end: Expected ';' after value"
+        );
+    }
+
+    #[test]
+    fn test_expression_lines() {
+        let line_of = |source: &str| {
+            let (tokens, _) = Scanner::new(source.to_string()).scan_all();
+            parse_expression(tokens).unwrap().line()
+        };
+
+        assert_eq!(line_of("1\n+ 2"), 2);
+        assert_eq!(line_of("\n-x"), 2);
+        assert_eq!(line_of("\n\n(1)"), 3);
+        assert_eq!(line_of("\n\"s\""), 2);
+        assert_eq!(line_of("a\n.b\n(1)"), 3);
+    }
+
+    #[test]
+    fn test_parse_expression_reports_recorded_errors() {
+        let args = vec!["1"; 300].join(", ");
+        let source = format!("clock({})", args);
+        let (tokens, _) = Scanner::new(source).scan_all();
+
+        let error = parse_expression(tokens).unwrap_err();
+        assert_eq!(error.message, "Can't have more than 255 arguments");
+    }
+
+    #[test]
+    fn test_synchronize_stops_at_const_and_switch() {
+        let errors = parse_errors("var x = 1 + ) const = 2;");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].message, "Expected expression");
+        assert_eq!(errors[1].token.token_type, TokenType::Equal);
+
+        let errors = parse_errors("print 1 + ) switch (x) { case 1 }");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].message, "Expected expression");
+        assert_eq!(errors[1].token.token_type, TokenType::RightBrace);
+    }
 }
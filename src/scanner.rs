@@ -6,7 +6,12 @@ lazy_static! {
     static ref KEYWORDS: HashMap<&'static str, TokenType> = {
         let mut map = HashMap::new();
         map.insert("and", TokenType::And);
+        map.insert("break", TokenType::Break);
+        map.insert("case", TokenType::Case);
         map.insert("class", TokenType::Class);
+        map.insert("const", TokenType::Const);
+        map.insert("continue", TokenType::Continue);
+        map.insert("default", TokenType::Default);
         map.insert("else", TokenType::Else);
         map.insert("false", TokenType::False);
         map.insert("fun", TokenType::Fun);
@@ -17,6 +22,7 @@ lazy_static! {
         map.insert("print", TokenType::Print);
         map.insert("return", TokenType::Return);
         map.insert("super", TokenType::Super);
+        map.insert("switch", TokenType::Switch);
         map.insert("this", TokenType::This);
         map.insert("true", TokenType::True);
         map.insert("var", TokenType::Var);
@@ -49,13 +55,21 @@ pub enum TokenType {
     RightParen,
     LeftBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
     Comma,
     Dot,
     Minus,
     Plus,
     Semicolon,
+    Colon,
+    Question,
     Slash,
     Star,
+    Percent,
+    Ampersand,
+    Pipe,
+    Caret,
 
     // One or two character tokens
     Bang,
@@ -64,17 +78,30 @@ pub enum TokenType {
     EqualEqual,
     Greater,
     GreaterEqual,
+    GreaterGreater,
     Less,
     LessEqual,
+    LessLess,
+    MinusEqual,
+    PlusEqual,
+    SlashEqual,
+    StarEqual,
+    StarStar,
 
     // Literals
     Identifier,
     String,
+    Char,
     Number,
 
     // Keywords
     And,
+    Break,
+    Case,
     Class,
+    Const,
+    Continue,
+    Default,
     Else,
     False,
     Fun,
@@ -85,6 +112,7 @@ pub enum TokenType {
     Print,
     Return,
     Super,
+    Switch,
     This,
     True,
     Var,
@@ -97,6 +125,8 @@ pub enum TokenType {
 pub enum LiteralValue {
     Number(f64),
     Str(String),
+    Char(char),
+    Bool(bool),
     Nil,
 }
 
@@ -105,6 +135,8 @@ impl fmt::Display for LiteralValue {
         match self {
             LiteralValue::Number(n) => write!(f, "{}", n),
             LiteralValue::Str(s) => write!(f, "\"{}\"", s),
+            LiteralValue::Char(c) => write!(f, "'{}'", c),
+            LiteralValue::Bool(b) => write!(f, "{}", b),
             LiteralValue::Nil => write!(f, "nil"),
         }
     }
@@ -115,6 +147,8 @@ pub struct Token {
     pub token_type: TokenType,
     lexeme: String,
     line: usize,
+    start: usize,
+    end: usize,
     pub value: Option<LiteralValue>,
 }
 
@@ -129,9 +163,19 @@ impl Token {
             token_type,
             lexeme,
             line,
+            start: 0,
+            end: 0,
             value,
         }
     }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn span(&self) -> (usize, usize) {
+        (self.start, self.end)
+    }
 }
 
 impl fmt::Display for Token {
@@ -144,7 +188,12 @@ pub struct Scanner {
     source: String,
     current: usize,
     line: usize,
+    column: usize,
     start: usize,
+    start_line: usize,
+    start_column: usize,
+    tab_width: usize,
+    max_comment_depth: usize,
     tokens: Vec<ScannerResult>,
 }
 
@@ -160,14 +209,33 @@ impl Scanner {
             source,
             current: 0,
             line: 1,
+            column: 1,
             start: 0,
+            start_line: 1,
+            start_column: 1,
+            tab_width: 1,
+            max_comment_depth: 255,
             tokens: Vec::new(),
         }
     }
 
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    pub fn with_max_comment_depth(mut self, max_comment_depth: usize) -> Self {
+        self.max_comment_depth = max_comment_depth;
+        self
+    }
+
     pub fn scan_tokens(&mut self) -> Vec<ScannerResult> {
+        self.skip_shebang();
+
         while !self.is_at_end() {
             self.start = self.current;
+            self.start_line = self.line;
+            self.start_column = self.column;
             self.scan_token();
         }
 
@@ -175,12 +243,38 @@ impl Scanner {
             token_type: TokenType::Eof,
             lexeme: "".to_string(),
             line: self.line,
+            start: self.current,
+            end: self.current,
             value: None,
         }));
 
         self.tokens.clone()
     }
 
+    pub fn scan_all(&mut self) -> (Vec<Token>, Vec<ScannerError>) {
+        let mut tokens = Vec::new();
+        let mut errors = Vec::new();
+
+        for result in self.scan_tokens() {
+            match result {
+                ScannerResult::Token(token) => tokens.push(token),
+                ScannerResult::Error(error) => errors.push(error),
+            }
+        }
+
+        (tokens, errors)
+    }
+
+    fn skip_shebang(&mut self) {
+        if self.current != 0 || !self.source.starts_with("#!") {
+            return;
+        }
+
+        while self.peek() != '\n' && !self.is_at_end() {
+            self.advance();
+        }
+    }
+
     fn scan_token(&mut self) {
         let c = self.advance();
 
@@ -189,12 +283,43 @@ impl Scanner {
             ')' => self.emit(TokenType::RightParen, None),
             '{' => self.emit(TokenType::LeftBrace, None),
             '}' => self.emit(TokenType::RightBrace, None),
+            '[' => self.emit(TokenType::LeftBracket, None),
+            ']' => self.emit(TokenType::RightBracket, None),
             ',' => self.emit(TokenType::Comma, None),
             '.' => self.emit(TokenType::Dot, None),
-            '-' => self.emit(TokenType::Minus, None),
-            '+' => self.emit(TokenType::Plus, None),
+            '-' => {
+                let token_type = if self.match_next('=') {
+                    TokenType::MinusEqual
+                } else {
+                    TokenType::Minus
+                };
+                self.emit(token_type, None);
+            }
+            '+' => {
+                let token_type = if self.match_next('=') {
+                    TokenType::PlusEqual
+                } else {
+                    TokenType::Plus
+                };
+                self.emit(token_type, None);
+            }
             ';' => self.emit(TokenType::Semicolon, None),
-            '*' => self.emit(TokenType::Star, None),
+            ':' => self.emit(TokenType::Colon, None),
+            '%' => self.emit(TokenType::Percent, None),
+            '&' => self.emit(TokenType::Ampersand, None),
+            '|' => self.emit(TokenType::Pipe, None),
+            '^' => self.emit(TokenType::Caret, None),
+            '?' => self.emit(TokenType::Question, None),
+            '*' => {
+                let token_type = if self.match_next('=') {
+                    TokenType::StarEqual
+                } else if self.match_next('*') {
+                    TokenType::StarStar
+                } else {
+                    TokenType::Star
+                };
+                self.emit(token_type, None);
+            }
             '!' => {
                 let token_type = if self.match_next('=') {
                     TokenType::BangEqual
@@ -206,6 +331,8 @@ impl Scanner {
             '<' => {
                 let token_type = if self.match_next('=') {
                     TokenType::LessEqual
+                } else if self.match_next('<') {
+                    TokenType::LessLess
                 } else {
                     TokenType::Less
                 };
@@ -214,6 +341,8 @@ impl Scanner {
             '>' => {
                 let token_type = if self.match_next('=') {
                     TokenType::GreaterEqual
+                } else if self.match_next('>') {
+                    TokenType::GreaterGreater
                 } else {
                     TokenType::Greater
                 };
@@ -229,20 +358,21 @@ impl Scanner {
             }
             '/' => self.scan_slash(),
             '"' => self.scan_string(),
+            '\'' => self.scan_char(),
             ' ' => {}
             '\r' => {}
             '\t' => {}
             '\n' => {
                 self.line += 1;
             }
-            c if c.is_digit(10) => self.scan_number(),
+            c if c.is_ascii_digit() => self.scan_number(),
             c if c.is_alphabetic() => self.scan_identifier_or_keyword(),
             _ => self.emit_error(format!("Unexpected character '{}'", c)),
         }
     }
 
     fn scan_identifier_or_keyword(&mut self) {
-        while self.peek().is_alphabetic() || self.peek().is_digit(10) || self.peek() == '_' {
+        while self.peek().is_alphabetic() || self.peek().is_ascii_digit() || self.peek() == '_' {
             self.advance();
         }
 
@@ -256,21 +386,21 @@ impl Scanner {
 
     fn emit_error(&mut self, message: String) {
         self.tokens.push(ScannerResult::Error(ScannerError {
-            line: self.line,
-            column: self.current,
+            line: self.start_line,
+            column: self.start_column,
             message,
         }));
     }
 
     fn scan_number(&mut self) {
-        while self.peek().is_digit(10) {
+        while self.peek().is_ascii_digit() {
             self.advance();
         }
 
-        if self.peek() == '.' {
+        if self.peek() == '.' && self.peek_next().is_ascii_digit() {
             self.advance();
 
-            while self.peek().is_digit(10) {
+            while self.peek().is_ascii_digit() {
                 self.advance();
             }
         }
@@ -298,21 +428,92 @@ impl Scanner {
         self.emit(TokenType::String, Some(LiteralValue::Str(value)));
     }
 
+    fn scan_char(&mut self) {
+        let mut chars = Vec::new();
+        let mut invalid_escape = None;
+        while self.peek() != '\'' && self.peek() != '\n' && !self.is_at_end() {
+            match self.advance() {
+                '\\' if !self.is_at_end() => {
+                    let escaped = self.advance();
+                    match unescape(escaped) {
+                        Some(c) => chars.push(c),
+                        None => {
+                            invalid_escape.get_or_insert(escaped);
+                        }
+                    }
+                }
+                c => chars.push(c),
+            }
+        }
+
+        // Consume the closing quote before reporting a bad escape, so it isn't
+        // mistaken for the start of another literal.
+        let terminated = self.peek() == '\'';
+        if terminated {
+            self.advance();
+        }
+
+        if let Some(escaped) = invalid_escape {
+            return self.emit_error(format!("Invalid escape sequence '\\{}'", escaped));
+        }
+        if !terminated {
+            return self.emit_error("Unterminated character literal".to_string());
+        }
+
+        match chars.as_slice() {
+            [c] => self.emit(TokenType::Char, Some(LiteralValue::Char(*c))),
+            [] => self.emit_error("Empty character literal".to_string()),
+            _ => self.emit_error("Character literal must contain a single character".to_string()),
+        }
+    }
+
     fn scan_slash(&mut self) {
         if self.match_next('/') {
-            while self.peek() != '\n' {
+            while self.peek() != '\n' && !self.is_at_end() {
                 self.advance();
             }
+        } else if self.match_next('*') {
+            self.scan_block_comment();
+        } else if self.match_next('=') {
+            self.emit(TokenType::SlashEqual, None);
         } else {
             self.emit(TokenType::Slash, None);
         }
     }
 
-    fn peek(&self) -> char {
-        if self.is_at_end() {
-            return '\0';
+    fn scan_block_comment(&mut self) {
+        let mut depth = 1;
+        let mut too_deep = false;
+
+        while depth > 0 {
+            if self.is_at_end() {
+                return self.emit_error("Unterminated block comment".to_string());
+            }
+
+            match self.advance() {
+                '\n' => self.line += 1,
+                '/' if self.match_next('*') => {
+                    depth += 1;
+                    if depth > self.max_comment_depth && !too_deep {
+                        too_deep = true;
+                        self.emit_error(format!(
+                            "Block comment nesting exceeds maximum depth of {}",
+                            self.max_comment_depth
+                        ));
+                    }
+                }
+                '*' if self.match_next('/') => depth -= 1,
+                _ => {}
+            }
         }
-        self.source.chars().nth(self.current).unwrap()
+    }
+
+    fn peek(&self) -> char {
+        self.source[self.current..].chars().next().unwrap_or('\0')
+    }
+
+    fn peek_next(&self) -> char {
+        self.source[self.current..].chars().nth(1).unwrap_or('\0')
     }
 
     fn match_next(&mut self, c: char) -> bool {
@@ -320,8 +521,8 @@ impl Scanner {
             return false;
         }
 
-        if self.source.chars().nth(self.current).unwrap() == c {
-            self.current += 1;
+        if self.peek() == c {
+            self.advance();
             return true;
         }
 
@@ -335,6 +536,8 @@ impl Scanner {
             token_type,
             lexeme,
             line,
+            start: self.start,
+            end: self.current,
             value,
         }));
     }
@@ -344,8 +547,368 @@ impl Scanner {
     }
 
     fn advance(&mut self) -> char {
-        let c = self.source.chars().nth(self.current).unwrap();
-        self.current += 1;
+        let c = self.peek();
+        self.current += c.len_utf8();
+        match c {
+            '\n' => self.column = 1,
+            '\t' => self.column += self.tab_width,
+            _ => self.column += 1,
+        }
         c
     }
 }
+
+fn unescape(c: char) -> Option<char> {
+    match c {
+        'n' => Some('\n'),
+        't' => Some('\t'),
+        'r' => Some('\r'),
+        '0' => Some('\0'),
+        '\\' => Some('\\'),
+        '\'' => Some('\''),
+        '"' => Some('"'),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_types(source: &str) -> Vec<TokenType> {
+        Scanner::new(source.to_string())
+            .scan_tokens()
+            .into_iter()
+            .filter_map(|t| match t {
+                ScannerResult::Token(t) => Some(t.token_type),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_token_display() {
+        let token = Token::new(TokenType::Star, String::from("*"), 1, None);
+        assert_eq!(token.to_string(), "*");
+    }
+
+    #[test]
+    fn test_token_spans() {
+        let tokens = Scanner::new("\"héllo\" + ñ".to_string()).scan_tokens();
+        let spans: Vec<(usize, usize)> = tokens
+            .iter()
+            .filter_map(|t| match t {
+                ScannerResult::Token(t) => Some(t.span()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(spans, vec![(0, 8), (9, 10), (11, 13), (13, 13)]);
+    }
+
+    #[test]
+    fn test_loop_control_keywords() {
+        assert_eq!(
+            token_types("break continue breaks"),
+            vec![
+                TokenType::Break,
+                TokenType::Continue,
+                TokenType::Identifier,
+                TokenType::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shebang_is_skipped() {
+        let tokens = Scanner::new("#!/usr/bin/env ilox\nprint 1;".to_string()).scan_tokens();
+        let tokens: Vec<(TokenType, usize)> = tokens
+            .into_iter()
+            .filter_map(|t| match t {
+                ScannerResult::Token(t) => Some((t.token_type, t.line)),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(
+            tokens,
+            vec![
+                (TokenType::Print, 2),
+                (TokenType::Number, 2),
+                (TokenType::Semicolon, 2),
+                (TokenType::Eof, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shebang_only_on_first_line() {
+        let tokens = Scanner::new("\n#!".to_string()).scan_tokens();
+        assert!(matches!(tokens[0], ScannerResult::Error(_)));
+    }
+
+    #[test]
+    fn test_char_literals() {
+        let tokens = Scanner::new("'a' '\\n'".to_string()).scan_tokens();
+        let values: Vec<String> = tokens
+            .iter()
+            .filter_map(|t| match t {
+                ScannerResult::Token(t) => t.value.as_ref().map(|v| v.to_string()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(values, vec!["'a'", "'\n'"]);
+    }
+
+    #[test]
+    fn test_invalid_char_literals() {
+        for source in ["''", "'ab'", "'a"] {
+            let tokens = Scanner::new(source.to_string()).scan_tokens();
+            assert!(
+                matches!(tokens[0], ScannerResult::Error(_)),
+                "expected an error for {}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn test_unterminated_string_reports_start() {
+        let (_, errors) = Scanner::new("1;\n  \"abc\ndef".to_string()).scan_all();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "[line 2:3] Error: Unterminated string"
+        );
+    }
+
+    #[test]
+    fn test_invalid_escape_recovers_after_literal() {
+        let (tokens, errors) = Scanner::new("'\\q' + 1;".to_string()).scan_all();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "[line 1:1] Error: Invalid escape sequence '\\q'"
+        );
+
+        let types: Vec<TokenType> = tokens.into_iter().map(|t| t.token_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                TokenType::Plus,
+                TokenType::Number,
+                TokenType::Semicolon,
+                TokenType::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_all_collects_errors() {
+        let (tokens, errors) = Scanner::new("1 @ + # 2".to_string()).scan_all();
+
+        let types: Vec<TokenType> = tokens.into_iter().map(|t| t.token_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                TokenType::Number,
+                TokenType::Plus,
+                TokenType::Number,
+                TokenType::Eof
+            ]
+        );
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn test_error_column_respects_tab_width() {
+        let (_, errors) = Scanner::new("\t@".to_string()).scan_all();
+        assert_eq!(errors[0].column, 2);
+
+        let (_, errors) = Scanner::new("\t@".to_string()).with_tab_width(4).scan_all();
+        assert_eq!(errors[0].column, 5);
+    }
+
+    #[test]
+    fn test_eof_line_without_trailing_newline() {
+        for source in ["1\n2\n3", "1\n\"a\nb\"\n// comment", "1 // comment"] {
+            let (tokens, _) = Scanner::new(source.to_string()).scan_all();
+            let eof = tokens.last().unwrap();
+
+            assert_eq!(eof.token_type, TokenType::Eof);
+            assert_eq!(eof.line, source.matches('\n').count() + 1);
+        }
+    }
+
+    #[test]
+    fn test_switch_keywords() {
+        assert_eq!(
+            token_types("switch case default"),
+            vec![
+                TokenType::Switch,
+                TokenType::Case,
+                TokenType::Default,
+                TokenType::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_const_keyword() {
+        assert_eq!(
+            token_types("const constant"),
+            vec![TokenType::Const, TokenType::Identifier, TokenType::Eof]
+        );
+    }
+
+    #[test]
+    fn test_peek_next_at_eof() {
+        let mut scanner = Scanner::new("ab".to_string());
+        assert_eq!(scanner.peek_next(), 'b');
+
+        scanner.advance();
+        assert_eq!(scanner.peek_next(), '\0');
+
+        scanner.advance();
+        assert_eq!(scanner.peek_next(), '\0');
+    }
+
+    #[test]
+    fn test_number_without_fraction_leaves_dot() {
+        assert_eq!(
+            token_types("1.foo"),
+            vec![
+                TokenType::Number,
+                TokenType::Dot,
+                TokenType::Identifier,
+                TokenType::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_block_comments() {
+        assert_eq!(
+            token_types("1 /* outer /* inner */\n still outer */ 2"),
+            vec![TokenType::Number, TokenType::Number, TokenType::Eof]
+        );
+
+        let (_, errors) = Scanner::new("/* /* */".to_string()).scan_all();
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_block_comment_depth_limit() {
+        let source = format!("{}{}", "/*".repeat(100_000), "*/".repeat(100_000));
+        let (tokens, errors) = Scanner::new(source).scan_all();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message,
+            "Block comment nesting exceeds maximum depth of 255"
+        );
+
+        let (_, errors) = Scanner::new("/* /* /* */ */ */".to_string())
+            .with_max_comment_depth(2)
+            .scan_all();
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_conditional_punctuation() {
+        assert_eq!(
+            token_types("? :"),
+            vec![TokenType::Question, TokenType::Colon, TokenType::Eof]
+        );
+    }
+
+    #[test]
+    fn test_compound_assignment_operators() {
+        assert_eq!(
+            token_types("+= -= *= /= + - * /"),
+            vec![
+                TokenType::PlusEqual,
+                TokenType::MinusEqual,
+                TokenType::StarEqual,
+                TokenType::SlashEqual,
+                TokenType::Plus,
+                TokenType::Minus,
+                TokenType::Star,
+                TokenType::Slash,
+                TokenType::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_power_operator() {
+        assert_eq!(
+            token_types("** * *="),
+            vec![
+                TokenType::StarStar,
+                TokenType::Star,
+                TokenType::StarEqual,
+                TokenType::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bitwise_operators() {
+        assert_eq!(
+            token_types("& | ^"),
+            vec![
+                TokenType::Ampersand,
+                TokenType::Pipe,
+                TokenType::Caret,
+                TokenType::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shift_operators() {
+        assert_eq!(
+            token_types("<< >> <= >="),
+            vec![
+                TokenType::LessLess,
+                TokenType::GreaterGreater,
+                TokenType::LessEqual,
+                TokenType::GreaterEqual,
+                TokenType::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(token_types("%"), vec![TokenType::Percent, TokenType::Eof]);
+    }
+
+    #[test]
+    fn test_brackets() {
+        assert_eq!(
+            token_types("[]"),
+            vec![
+                TokenType::LeftBracket,
+                TokenType::RightBracket,
+                TokenType::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_literal_value_display() {
+        assert_eq!(LiteralValue::Number(1.5).to_string(), "1.5");
+        assert_eq!(LiteralValue::Number(3.0).to_string(), "3");
+        assert_eq!(LiteralValue::Str("hi".to_string()).to_string(), "\"hi\"");
+        assert_eq!(LiteralValue::Char('c').to_string(), "'c'");
+        assert_eq!(LiteralValue::Bool(true).to_string(), "true");
+        assert_eq!(LiteralValue::Bool(false).to_string(), "false");
+        assert_eq!(LiteralValue::Nil.to_string(), "nil");
+    }
+}
@@ -0,0 +1,678 @@
+use crate::{
+    expr::{AstPrinter, Expression, JsonPrinter, Visitable as _},
+    scanner::Token,
+};
+
+pub trait StmtVisitor {
+    type Output;
+
+    fn visit_block_stmt(&mut self, stmt: &Block) -> Self::Output;
+    fn visit_break_stmt(&mut self, stmt: &Break) -> Self::Output;
+    fn visit_class_stmt(&mut self, stmt: &Class) -> Self::Output;
+    fn visit_const_stmt(&mut self, stmt: &Const) -> Self::Output;
+    fn visit_continue_stmt(&mut self, stmt: &Continue) -> Self::Output;
+    fn visit_expression_stmt(&mut self, stmt: &ExpressionStmt) -> Self::Output;
+    fn visit_function_stmt(&mut self, stmt: &Function) -> Self::Output;
+    fn visit_if_stmt(&mut self, stmt: &If) -> Self::Output;
+    fn visit_print_stmt(&mut self, stmt: &Print) -> Self::Output;
+    fn visit_return_stmt(&mut self, stmt: &Return) -> Self::Output;
+    fn visit_switch_stmt(&mut self, stmt: &Switch) -> Self::Output;
+    fn visit_var_stmt(&mut self, stmt: &Var) -> Self::Output;
+    fn visit_while_stmt(&mut self, stmt: &While) -> Self::Output;
+}
+
+impl AstPrinter {
+    pub fn print_stmt(stmt: &Stmt) -> String {
+        stmt.accept(&mut AstPrinter)
+    }
+
+    pub fn print_program(statements: &[Stmt]) -> String {
+        let lines: Vec<String> = statements.iter().map(AstPrinter::print_stmt).collect();
+        lines.join("\n")
+    }
+
+    fn parenthesize_stmts(&mut self, head: String, statements: &[Stmt]) -> String {
+        let mut parts = vec![head];
+        parts.extend(statements.iter().map(|stmt| stmt.accept(self)));
+        format!("({})", parts.join(" "))
+    }
+}
+
+impl StmtVisitor for AstPrinter {
+    type Output = String;
+
+    fn visit_block_stmt(&mut self, stmt: &Block) -> Self::Output {
+        self.parenthesize_stmts("block".to_string(), &stmt.statements)
+    }
+
+    fn visit_break_stmt(&mut self, _stmt: &Break) -> Self::Output {
+        "(break)".to_string()
+    }
+
+    fn visit_class_stmt(&mut self, stmt: &Class) -> Self::Output {
+        let mut parts = vec![format!("class {}", stmt.name)];
+        if let Some(superclass) = &stmt.superclass {
+            parts.push(format!("< {}", superclass.accept(self)));
+        }
+        parts.extend(stmt.methods.iter().map(|m| self.visit_function_stmt(m)));
+        format!("({})", parts.join(" "))
+    }
+
+    fn visit_const_stmt(&mut self, stmt: &Const) -> Self::Output {
+        format!("(const {} {})", stmt.name, stmt.initializer.accept(self))
+    }
+
+    fn visit_continue_stmt(&mut self, _stmt: &Continue) -> Self::Output {
+        "(continue)".to_string()
+    }
+
+    fn visit_expression_stmt(&mut self, stmt: &ExpressionStmt) -> Self::Output {
+        format!("(; {})", stmt.expr.accept(self))
+    }
+
+    fn visit_function_stmt(&mut self, stmt: &Function) -> Self::Output {
+        let params: Vec<String> = stmt.params.iter().map(|p| p.to_string()).collect();
+        let head = format!("fun {} ({})", stmt.name, params.join(" "));
+        self.parenthesize_stmts(head, &stmt.body)
+    }
+
+    fn visit_if_stmt(&mut self, stmt: &If) -> Self::Output {
+        let condition = stmt.condition.accept(self);
+        let then_branch = stmt.then_branch.accept(self);
+        match &stmt.else_branch {
+            Some(else_branch) => format!(
+                "(if {} {} {})",
+                condition,
+                then_branch,
+                else_branch.accept(self)
+            ),
+            None => format!("(if {} {})", condition, then_branch),
+        }
+    }
+
+    fn visit_print_stmt(&mut self, stmt: &Print) -> Self::Output {
+        format!("(print {})", stmt.expr.accept(self))
+    }
+
+    fn visit_return_stmt(&mut self, stmt: &Return) -> Self::Output {
+        match &stmt.value {
+            Some(value) => format!("(return {})", value.accept(self)),
+            None => "(return)".to_string(),
+        }
+    }
+
+    fn visit_switch_stmt(&mut self, stmt: &Switch) -> Self::Output {
+        let mut parts = vec![format!("switch {}", stmt.discriminant.accept(self))];
+        for (value, body) in &stmt.cases {
+            let head = format!("case {}", value.accept(self));
+            parts.push(self.parenthesize_stmts(head, body));
+        }
+        if let Some(default) = &stmt.default {
+            parts.push(self.parenthesize_stmts("default".to_string(), default));
+        }
+        format!("({})", parts.join(" "))
+    }
+
+    fn visit_var_stmt(&mut self, stmt: &Var) -> Self::Output {
+        match &stmt.initializer {
+            Some(initializer) => format!("(var {} {})", stmt.name, initializer.accept(self)),
+            None => format!("(var {})", stmt.name),
+        }
+    }
+
+    fn visit_while_stmt(&mut self, stmt: &While) -> Self::Output {
+        format!(
+            "(while {} {})",
+            stmt.condition.accept(self),
+            stmt.body.accept(self)
+        )
+    }
+}
+
+impl JsonPrinter {
+    pub fn print_stmt(stmt: &Stmt) -> String {
+        stmt.accept(&mut JsonPrinter)
+    }
+
+    pub fn print_program(statements: &[Stmt]) -> String {
+        JsonPrinter::list(statements.iter().map(JsonPrinter::print_stmt).collect())
+    }
+
+    fn statements(&mut self, statements: &[Stmt]) -> String {
+        JsonPrinter::list(statements.iter().map(|stmt| stmt.accept(self)).collect())
+    }
+
+    fn optional(&mut self, expr: &Option<Expression>) -> String {
+        match expr {
+            Some(expr) => expr.accept(self),
+            None => "null".to_string(),
+        }
+    }
+}
+
+impl StmtVisitor for JsonPrinter {
+    type Output = String;
+
+    fn visit_block_stmt(&mut self, stmt: &Block) -> Self::Output {
+        let fields = vec![("statements", self.statements(&stmt.statements))];
+        JsonPrinter::node("Block", fields)
+    }
+
+    fn visit_break_stmt(&mut self, _stmt: &Break) -> Self::Output {
+        JsonPrinter::node("Break", Vec::new())
+    }
+
+    fn visit_class_stmt(&mut self, stmt: &Class) -> Self::Output {
+        let methods = stmt
+            .methods
+            .iter()
+            .map(|m| self.visit_function_stmt(m))
+            .collect();
+        let fields = vec![
+            ("name", JsonPrinter::token(&stmt.name)),
+            ("superclass", self.optional(&stmt.superclass)),
+            ("methods", JsonPrinter::list(methods)),
+        ];
+        JsonPrinter::node("Class", fields)
+    }
+
+    fn visit_const_stmt(&mut self, stmt: &Const) -> Self::Output {
+        let fields = vec![
+            ("name", JsonPrinter::token(&stmt.name)),
+            ("initializer", stmt.initializer.accept(self)),
+        ];
+        JsonPrinter::node("Const", fields)
+    }
+
+    fn visit_continue_stmt(&mut self, _stmt: &Continue) -> Self::Output {
+        JsonPrinter::node("Continue", Vec::new())
+    }
+
+    fn visit_expression_stmt(&mut self, stmt: &ExpressionStmt) -> Self::Output {
+        JsonPrinter::node("Expression", vec![("expression", stmt.expr.accept(self))])
+    }
+
+    fn visit_function_stmt(&mut self, stmt: &Function) -> Self::Output {
+        let params = stmt.params.iter().map(JsonPrinter::token).collect();
+        let fields = vec![
+            ("name", JsonPrinter::token(&stmt.name)),
+            ("params", JsonPrinter::list(params)),
+            ("body", self.statements(&stmt.body)),
+        ];
+        JsonPrinter::node("Function", fields)
+    }
+
+    fn visit_if_stmt(&mut self, stmt: &If) -> Self::Output {
+        let else_branch = match &stmt.else_branch {
+            Some(else_branch) => else_branch.accept(self),
+            None => "null".to_string(),
+        };
+        let fields = vec![
+            ("condition", stmt.condition.accept(self)),
+            ("then", stmt.then_branch.accept(self)),
+            ("else", else_branch),
+        ];
+        JsonPrinter::node("If", fields)
+    }
+
+    fn visit_print_stmt(&mut self, stmt: &Print) -> Self::Output {
+        JsonPrinter::node("Print", vec![("expression", stmt.expr.accept(self))])
+    }
+
+    fn visit_return_stmt(&mut self, stmt: &Return) -> Self::Output {
+        JsonPrinter::node("Return", vec![("value", self.optional(&stmt.value))])
+    }
+
+    fn visit_switch_stmt(&mut self, stmt: &Switch) -> Self::Output {
+        let cases = stmt
+            .cases
+            .iter()
+            .map(|(value, body)| {
+                let value = value.accept(self);
+                let body = self.statements(body);
+                format!("{{\"value\":{},\"body\":{}}}", value, body)
+            })
+            .collect();
+        let default = match &stmt.default {
+            Some(default) => self.statements(default),
+            None => "null".to_string(),
+        };
+        let fields = vec![
+            ("discriminant", stmt.discriminant.accept(self)),
+            ("cases", JsonPrinter::list(cases)),
+            ("default", default),
+        ];
+        JsonPrinter::node("Switch", fields)
+    }
+
+    fn visit_var_stmt(&mut self, stmt: &Var) -> Self::Output {
+        let fields = vec![
+            ("name", JsonPrinter::token(&stmt.name)),
+            ("initializer", self.optional(&stmt.initializer)),
+        ];
+        JsonPrinter::node("Var", fields)
+    }
+
+    fn visit_while_stmt(&mut self, stmt: &While) -> Self::Output {
+        let fields = vec![
+            ("condition", stmt.condition.accept(self)),
+            ("body", stmt.body.accept(self)),
+        ];
+        JsonPrinter::node("While", fields)
+    }
+}
+
+#[derive(Default)]
+pub struct PrettyPrinter {
+    depth: usize,
+}
+
+impl PrettyPrinter {
+    pub fn print(statements: &[Stmt]) -> String {
+        let mut printer = PrettyPrinter::default();
+        let lines: Vec<String> = statements.iter().map(|s| s.accept(&mut printer)).collect();
+        lines.join("\n")
+    }
+
+    fn line(&self, text: &str) -> String {
+        format!("{}{}", "    ".repeat(self.depth), text)
+    }
+
+    fn nested(&mut self, statements: &[Stmt]) -> Vec<String> {
+        self.depth += 1;
+        let lines = statements.iter().map(|stmt| stmt.accept(self)).collect();
+        self.depth -= 1;
+        lines
+    }
+
+    fn with_body(&mut self, header: String, body: &[Stmt]) -> String {
+        let mut lines = vec![self.line(&header)];
+        lines.extend(self.nested(body));
+        lines.join("\n")
+    }
+}
+
+impl StmtVisitor for PrettyPrinter {
+    type Output = String;
+
+    fn visit_block_stmt(&mut self, stmt: &Block) -> Self::Output {
+        let mut lines = vec![self.line("{")];
+        lines.extend(self.nested(&stmt.statements));
+        lines.push(self.line("}"));
+        lines.join("\n")
+    }
+
+    fn visit_break_stmt(&mut self, _stmt: &Break) -> Self::Output {
+        self.line("break")
+    }
+
+    fn visit_class_stmt(&mut self, stmt: &Class) -> Self::Output {
+        let mut header = format!("class {}", stmt.name);
+        if let Some(superclass) = &stmt.superclass {
+            header.push_str(&format!(" < {}", AstPrinter::print(superclass)));
+        }
+
+        let mut lines = vec![self.line(&header)];
+        self.depth += 1;
+        lines.extend(stmt.methods.iter().map(|m| self.visit_function_stmt(m)));
+        self.depth -= 1;
+        lines.join("\n")
+    }
+
+    fn visit_const_stmt(&mut self, stmt: &Const) -> Self::Output {
+        let initializer = AstPrinter::print(&stmt.initializer);
+        self.line(&format!("const {} = {}", stmt.name, initializer))
+    }
+
+    fn visit_continue_stmt(&mut self, _stmt: &Continue) -> Self::Output {
+        self.line("continue")
+    }
+
+    fn visit_expression_stmt(&mut self, stmt: &ExpressionStmt) -> Self::Output {
+        self.line(&AstPrinter::print(&stmt.expr))
+    }
+
+    fn visit_function_stmt(&mut self, stmt: &Function) -> Self::Output {
+        let params: Vec<String> = stmt.params.iter().map(|p| p.to_string()).collect();
+        let header = format!("fun {}({})", stmt.name, params.join(", "));
+        self.with_body(header, &stmt.body)
+    }
+
+    fn visit_if_stmt(&mut self, stmt: &If) -> Self::Output {
+        let header = format!("if {}", AstPrinter::print(&stmt.condition));
+        let mut lines = vec![self.with_body(header, std::slice::from_ref(&stmt.then_branch))];
+        if let Some(else_branch) = &stmt.else_branch {
+            lines.push(self.with_body("else".to_string(), std::slice::from_ref(else_branch)));
+        }
+        lines.join("\n")
+    }
+
+    fn visit_print_stmt(&mut self, stmt: &Print) -> Self::Output {
+        self.line(&format!("print {}", AstPrinter::print(&stmt.expr)))
+    }
+
+    fn visit_return_stmt(&mut self, stmt: &Return) -> Self::Output {
+        match &stmt.value {
+            Some(value) => self.line(&format!("return {}", AstPrinter::print(value))),
+            None => self.line("return"),
+        }
+    }
+
+    fn visit_switch_stmt(&mut self, stmt: &Switch) -> Self::Output {
+        let mut lines =
+            vec![self.line(&format!("switch {}", AstPrinter::print(&stmt.discriminant)))];
+        self.depth += 1;
+        for (value, body) in &stmt.cases {
+            let header = format!("case {}:", AstPrinter::print(value));
+            lines.push(self.with_body(header, body));
+        }
+        if let Some(default) = &stmt.default {
+            lines.push(self.with_body("default:".to_string(), default));
+        }
+        self.depth -= 1;
+        lines.join("\n")
+    }
+
+    fn visit_var_stmt(&mut self, stmt: &Var) -> Self::Output {
+        match &stmt.initializer {
+            Some(initializer) => self.line(&format!(
+                "var {} = {}",
+                stmt.name,
+                AstPrinter::print(initializer)
+            )),
+            None => self.line(&format!("var {}", stmt.name)),
+        }
+    }
+
+    fn visit_while_stmt(&mut self, stmt: &While) -> Self::Output {
+        let header = format!("while {}", AstPrinter::print(&stmt.condition));
+        self.with_body(header, std::slice::from_ref(&stmt.body))
+    }
+}
+
+pub trait Visitable {
+    fn accept<V: StmtVisitor>(&self, visitor: &mut V) -> V::Output;
+}
+
+#[derive(Debug, Clone)]
+pub enum Stmt {
+    Block(Block),
+    Break(Break),
+    Class(Class),
+    Const(Const),
+    Continue(Continue),
+    Expression(ExpressionStmt),
+    Function(Function),
+    If(If),
+    Print(Print),
+    Return(Return),
+    Switch(Switch),
+    Var(Var),
+    While(While),
+}
+
+impl Visitable for Stmt {
+    fn accept<V: StmtVisitor>(&self, visitor: &mut V) -> V::Output {
+        match self {
+            Stmt::Block(stmt) => visitor.visit_block_stmt(stmt),
+            Stmt::Break(stmt) => visitor.visit_break_stmt(stmt),
+            Stmt::Class(stmt) => visitor.visit_class_stmt(stmt),
+            Stmt::Const(stmt) => visitor.visit_const_stmt(stmt),
+            Stmt::Continue(stmt) => visitor.visit_continue_stmt(stmt),
+            Stmt::Expression(stmt) => visitor.visit_expression_stmt(stmt),
+            Stmt::Function(stmt) => visitor.visit_function_stmt(stmt),
+            Stmt::If(stmt) => visitor.visit_if_stmt(stmt),
+            Stmt::Print(stmt) => visitor.visit_print_stmt(stmt),
+            Stmt::Return(stmt) => visitor.visit_return_stmt(stmt),
+            Stmt::Switch(stmt) => visitor.visit_switch_stmt(stmt),
+            Stmt::Var(stmt) => visitor.visit_var_stmt(stmt),
+            Stmt::While(stmt) => visitor.visit_while_stmt(stmt),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub statements: Vec<Stmt>,
+}
+
+impl Block {
+    pub fn new(statements: Vec<Stmt>) -> Self {
+        Self { statements }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Break {
+    pub keyword: Token,
+}
+
+impl Break {
+    pub fn new(keyword: Token) -> Self {
+        Self { keyword }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Class {
+    pub name: Token,
+    pub superclass: Option<Expression>,
+    pub methods: Vec<Function>,
+}
+
+impl Class {
+    pub fn new(name: Token, superclass: Option<Expression>, methods: Vec<Function>) -> Self {
+        Self {
+            name,
+            superclass,
+            methods,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Const {
+    pub name: Token,
+    pub initializer: Expression,
+}
+
+impl Const {
+    pub fn new(name: Token, initializer: Expression) -> Self {
+        Self { name, initializer }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Continue {
+    pub keyword: Token,
+}
+
+impl Continue {
+    pub fn new(keyword: Token) -> Self {
+        Self { keyword }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExpressionStmt {
+    pub expr: Expression,
+}
+
+impl ExpressionStmt {
+    pub fn new(expr: Expression) -> Self {
+        Self { expr }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub name: Token,
+    pub params: Vec<Token>,
+    pub body: Vec<Stmt>,
+}
+
+impl Function {
+    pub fn new(name: Token, params: Vec<Token>, body: Vec<Stmt>) -> Self {
+        Self { name, params, body }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct If {
+    pub condition: Expression,
+    pub then_branch: Box<Stmt>,
+    pub else_branch: Option<Box<Stmt>>,
+}
+
+impl If {
+    pub fn new(
+        condition: Expression,
+        then_branch: Box<Stmt>,
+        else_branch: Option<Box<Stmt>>,
+    ) -> Self {
+        Self {
+            condition,
+            then_branch,
+            else_branch,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Print {
+    pub keyword: Token,
+    pub expr: Expression,
+}
+
+impl Print {
+    pub fn new(keyword: Token, expr: Expression) -> Self {
+        Self { keyword, expr }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Return {
+    pub keyword: Token,
+    pub value: Option<Expression>,
+}
+
+impl Return {
+    pub fn new(keyword: Token, value: Option<Expression>) -> Self {
+        Self { keyword, value }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Switch {
+    pub discriminant: Expression,
+    pub cases: Vec<(Expression, Vec<Stmt>)>,
+    pub default: Option<Vec<Stmt>>,
+}
+
+impl Switch {
+    pub fn new(
+        discriminant: Expression,
+        cases: Vec<(Expression, Vec<Stmt>)>,
+        default: Option<Vec<Stmt>>,
+    ) -> Self {
+        Self {
+            discriminant,
+            cases,
+            default,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Var {
+    pub name: Token,
+    pub initializer: Option<Expression>,
+}
+
+impl Var {
+    pub fn new(name: Token, initializer: Option<Expression>) -> Self {
+        Self { name, initializer }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct While {
+    pub condition: Expression,
+    pub body: Box<Stmt>,
+}
+
+impl While {
+    pub fn new(condition: Expression, body: Box<Stmt>) -> Self {
+        Self { condition, body }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser, scanner::Scanner};
+
+    use super::*;
+
+    fn print_program(source: &str) -> String {
+        let (tokens, _) = Scanner::new(source.to_string()).scan_all();
+        AstPrinter::print_program(&parser::parse(tokens).unwrap())
+    }
+
+    #[test]
+    fn test_print_statements() {
+        let source = "var a = 1;\nprint a + 2;\n{ var b; b = a; }\na;";
+        assert_eq!(
+            print_program(source),
+            "(var a 1)\n(print (+ a 2))\n(block (var b) (; (= b a)))\n(; a)"
+        );
+    }
+
+    #[test]
+    fn test_print_control_flow_and_declarations() {
+        assert_eq!(
+            print_program("if (a) print 1; else while (b) break;"),
+            "(if a (print 1) (while b (break)))"
+        );
+        assert_eq!(
+            print_program("fun add(a, b) { return a + b; }"),
+            "(fun add (a b) (return (+ a b)))"
+        );
+        assert_eq!(
+            print_program("class B < A { init() { return; } }"),
+            "(class B < A (fun init () (return)))"
+        );
+        assert_eq!(
+            print_program("switch (x) { case 1: print 1; default: continue; }"),
+            "(switch x (case 1 (print 1)) (default (continue)))"
+        );
+    }
+
+    #[test]
+    fn test_json_statements() {
+        let (tokens, _) = Scanner::new("var a;\nprint a;".to_string()).scan_all();
+        assert_eq!(
+            JsonPrinter::print_program(&parser::parse(tokens).unwrap()),
+            r#"[{"type":"Var","name":"a","initializer":null},{"type":"Print","expression":{"type":"Variable","name":"a"}}]"#
+        );
+    }
+
+    #[test]
+    fn test_pretty_printer() {
+        let source = "{ var a = 1; if (a > 0) { print a; } else print -a; }";
+        let (tokens, _) = Scanner::new(source.to_string()).scan_all();
+        let statements = parser::parse(tokens).unwrap();
+
+        let expected = [
+            "{",
+            "    var a = 1",
+            "    if (> a 0)",
+            "        {",
+            "            print a",
+            "        }",
+            "    else",
+            "        print (- a)",
+            "}",
+        ];
+        assert_eq!(PrettyPrinter::print(&statements), expected.join("\n"));
+    }
+}
@@ -0,0 +1,65 @@
+use std::{fmt, rc::Rc};
+
+use crate::{callable::Callable, scanner::LiteralValue};
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Number(f64),
+    Str(String),
+    Bool(bool),
+    Nil,
+    Callable(Rc<dyn Callable>),
+}
+
+// Callables are compared by identity.
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Number(a), Value::Number(b)) => a == b,
+            (Value::Str(a), Value::Str(b)) => a == b,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Nil, Value::Nil) => true,
+            (Value::Callable(a), Value::Callable(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Str(s) => write!(f, "{}", s),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Nil => write!(f, "nil"),
+            Value::Callable(callable) => write!(f, "{}", callable),
+        }
+    }
+}
+
+impl From<&LiteralValue> for Value {
+    fn from(value: &LiteralValue) -> Self {
+        match value {
+            LiteralValue::Number(n) => Value::Number(*n),
+            LiteralValue::Str(s) => Value::Str(s.clone()),
+            LiteralValue::Char(c) => Value::Str(c.to_string()),
+            LiteralValue::Bool(b) => Value::Bool(*b),
+            LiteralValue::Nil => Value::Nil,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Value::Number(3.0).to_string(), "3");
+        assert_eq!(Value::Number(3.5).to_string(), "3.5");
+        assert_eq!(Value::Number(-0.25).to_string(), "-0.25");
+        assert_eq!(Value::Str("hi".to_string()).to_string(), "hi");
+        assert_eq!(Value::Bool(true).to_string(), "true");
+        assert_eq!(Value::Nil.to_string(), "nil");
+    }
+}